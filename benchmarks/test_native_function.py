@@ -0,0 +1,56 @@
+from wasmer import Store, Module, Instance
+import os
+
+here = os.path.dirname(os.path.realpath(__file__))
+
+TEST_BYTES = """
+(module
+  (type (func (param i32 i32) (result i32)))
+  (func (type 0)
+    local.get 0
+    local.get 1
+    i32.add)
+  (export "sum" (func 0)))
+"""
+NBODY_BYTES = open(here + '/nbody.wasm', 'rb').read()
+
+N = 10000
+NBODY_N = 5000
+
+def test_benchmark_sum_with_function(benchmark):
+    sum = Instance(Module(Store(), TEST_BYTES)).exports.sum
+
+    def bench():
+        for i in range(N):
+            sum(i, 1)
+
+        return True
+
+    assert benchmark(bench)
+
+def test_benchmark_sum_with_native_function(benchmark):
+    sum = Instance(Module(Store(), TEST_BYTES)).exports.sum.native()
+
+    def bench():
+        for i in range(N):
+            sum(i, 1)
+
+        return True
+
+    assert benchmark(bench)
+
+def test_benchmark_nbody_with_function(benchmark):
+    nbody = Instance(Module(Store(), NBODY_BYTES)).exports.main
+
+    def bench():
+        return nbody(NBODY_N)
+
+    assert benchmark(bench)
+
+def test_benchmark_nbody_with_native_function(benchmark):
+    nbody = Instance(Module(Store(), NBODY_BYTES)).exports.main.native()
+
+    def bench():
+        return nbody(NBODY_N)
+
+    assert benchmark(bench)
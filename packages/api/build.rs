@@ -1,6 +1,59 @@
+use std::{env, fs, path::PathBuf};
+
+/// Exposes the version of the `wasmer` crate this package is built
+/// against as the `WASMER_VERSION` environment variable.
+///
+/// The version is taken from the `WASMER_VERSION` environment
+/// variable if it is set, or else read from the closest lock file,
+/// i.e. the one of the workspace, or the one shipped with a source
+/// distribution. Without any, the version is `unknown`: the build
+/// must not fail because of it.
 fn main() {
-    println!(
-        "cargo:rustc-env=WASMER_VERSION={}",
-        env!("CARGO_PKG_VERSION")
-    );
+    println!("cargo:rerun-if-env-changed=WASMER_VERSION");
+
+    let wasmer_version = env::var("WASMER_VERSION")
+        .ok()
+        .or_else(wasmer_version_from_lock)
+        .unwrap_or_else(|| {
+            println!("cargo:warning=The version of the `wasmer` crate cannot be found in any lock file; set `WASMER_VERSION` to provide it");
+
+            "unknown".to_string()
+        });
+
+    println!("cargo:rustc-env=WASMER_VERSION={}", wasmer_version);
+}
+
+fn wasmer_version_from_lock() -> Option<String> {
+    let manifest_directory = PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?);
+    let lock_path = manifest_directory
+        .ancestors()
+        .map(|directory| directory.join("Cargo.lock"))
+        .find(|lock_path| lock_path.is_file())?;
+
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+
+    let lock = fs::read_to_string(&lock_path).ok()?;
+
+    // This package is named `wasmer` too, but it has no source.
+    lock.split("[[package]]").find_map(|package| {
+        let mut name = None;
+        let mut version = None;
+        let mut has_source = false;
+
+        for line in package.lines() {
+            let mut key_value = line.splitn(2, " = ");
+
+            match (key_value.next(), key_value.next()) {
+                (Some("name"), Some(value)) => name = Some(value.trim_matches('"')),
+                (Some("version"), Some(value)) => version = Some(value.trim_matches('"')),
+                (Some("source"), Some(_)) => has_source = true,
+                _ => (),
+            }
+        }
+
+        match name {
+            Some("wasmer") if has_source => version.map(ToString::to_string),
+            _ => None,
+        }
+    })
 }
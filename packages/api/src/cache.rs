@@ -0,0 +1,146 @@
+use crate::{
+    names::ParameterNames,
+    serialization::{self, Header},
+    store::Store,
+    wasmer_inner::{wasmer, wasmer_cache},
+};
+use memmap2::Mmap;
+use pyo3::{exceptions::RuntimeWarning, prelude::*};
+use std::{
+    fs::{self, File},
+    path::PathBuf,
+    process,
+};
+
+/// A cache of compiled modules, stored on the file system, in the
+/// `path` directory. The directory is created if it does not exist.
+///
+/// The cache is used with `Module(store, bytes, cache=…)`: the first
+/// time a module is compiled, its artifact is stored in the cache;
+/// the next times (e.g. in another process), the artifact is loaded
+/// from the cache instead of being compiled again.
+///
+/// The artifacts are keyed by a hash of the WebAssembly bytes, the
+/// engine, the compiler, the metering, features and limits
+/// configuration, the target and the Wasmer version. Consequently,
+/// changing any of them compiles the module again.
+///
+/// The artifacts are stored in the same format as
+/// `Module.serialize`, and are checked the same way before being
+/// loaded: a corrupted or incompatible artifact is ignored, and the
+/// module is compiled again. Storing an artifact is best-effort too:
+/// if it fails, a `RuntimeWarning` is raised, and the module is used
+/// anyway.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import cache, Store, Module
+/// import tempfile
+///
+/// store = Store()
+/// file_system_cache = cache.FileSystemCache(tempfile.mkdtemp())
+///
+/// # The module is compiled, and stored in the cache.
+/// module = Module(store, '(module)', cache=file_system_cache)
+///
+/// # The module is loaded from the cache.
+/// module = Module(store, '(module)', cache=file_system_cache)
+/// ```
+#[pyclass]
+#[text_signature = "(path)"]
+pub struct FileSystemCache {
+    path: String,
+}
+
+impl FileSystemCache {
+    /// The path of the artifact of the module compiled from `bytes`.
+    fn artifact_path(&self, store: &Store, header: &Header, bytes: &[u8]) -> PathBuf {
+        let metadata = format!("{}\0{}\0", store.fingerprint(), header.encode());
+        let key = wasmer_cache::Hash::generate(&[metadata.as_bytes(), bytes].concat());
+
+        PathBuf::from(&self.path).join(key.to_string())
+    }
+
+    /// Loads the module compiled from `bytes`, if it is in the cache.
+    /// A corrupted or incompatible artifact is considered missing.
+    pub(crate) fn load(&self, store: &Store, bytes: &[u8]) -> Option<wasmer::Module> {
+        let header = Header::new(store);
+        let file = File::open(self.artifact_path(store, &header, bytes)).ok()?;
+        let sealed_artifact = unsafe { Mmap::map(&file) }.ok()?;
+        let (_, artifact) = serialization::open(&header, &sealed_artifact).ok()?;
+
+        unsafe { wasmer::Module::deserialize(store.inner(), artifact) }.ok()
+    }
+
+    /// Stores the module compiled from `bytes` in the cache. Storing
+    /// is best-effort: the module has been compiled anyway, so a
+    /// failure only raises a `RuntimeWarning`.
+    pub(crate) fn store(
+        &self,
+        py: Python,
+        store: &Store,
+        bytes: &[u8],
+        module: &wasmer::Module,
+        parameter_names: &ParameterNames,
+    ) -> PyResult<()> {
+        match self.try_store(store, bytes, module, parameter_names) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                py.import("warnings")?.call1(
+                    "warn",
+                    (
+                        format!("The module cannot be stored in the cache: {}", error),
+                        py.get_type::<RuntimeWarning>(),
+                    ),
+                )?;
+
+                Ok(())
+            }
+        }
+    }
+
+    fn try_store(
+        &self,
+        store: &Store,
+        bytes: &[u8],
+        module: &wasmer::Module,
+        parameter_names: &ParameterNames,
+    ) -> Result<(), String> {
+        let header = Header::new(store);
+        let artifact = module.serialize().map_err(|error| error.to_string())?;
+        let path = self.artifact_path(store, &header, bytes);
+
+        // Write a temporary file first, and then rename it, so that
+        // another process never loads a partially written artifact.
+        // The temporary file is removed if anything fails.
+        let temporary_path = path.with_extension(format!("{}.tmp", process::id()));
+
+        fs::write(
+            &temporary_path,
+            serialization::seal(&header, parameter_names, &artifact),
+        )
+        .and_then(|()| fs::rename(&temporary_path, &path))
+        .map_err(|error| {
+            let _ = fs::remove_file(&temporary_path);
+
+            error.to_string()
+        })
+    }
+}
+
+#[pymethods]
+impl FileSystemCache {
+    #[new]
+    fn new(path: String) -> PyResult<Self> {
+        fs::create_dir_all(&path)?;
+
+        Ok(Self { path })
+    }
+
+    /// The path of the directory where the artifacts are stored.
+    #[getter]
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
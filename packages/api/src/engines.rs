@@ -1 +1 @@
-pub use wasmer_engines::{Native, JIT};
+pub use wasmer_engines::{Features, Metering, Native, JIT};
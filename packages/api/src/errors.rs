@@ -1,5 +1,10 @@
-use pyo3::{prelude::*, type_object::PyTypeObject};
-use std::string::ToString;
+use crate::{
+    metering::Metering,
+    traps::{FrameInfo, TrapCode},
+    wasmer_inner::wasmer,
+};
+use pyo3::{create_exception, exceptions::RuntimeError, prelude::*, type_object::PyTypeObject};
+use std::{error::Error, fmt, string::ToString};
 
 pub fn to_py_err<PyError, Error>(error: Error) -> PyErr
 where
@@ -8,3 +13,202 @@ where
 {
     PyErr::new::<PyError, _>(error.to_string())
 }
+
+// The exceptions live in the `wasmer.errors` module, but
+// `create_exception!` accepts a single identifier as the module
+// name; their `__module__` is fixed when the module is initialized.
+create_exception!(wasmer, CompileError, RuntimeError);
+create_exception!(wasmer, ValidationError, CompileError);
+create_exception!(wasmer, InstantiationError, RuntimeError);
+create_exception!(wasmer, LinkError, InstantiationError);
+create_exception!(wasmer, Trap, RuntimeError);
+create_exception!(wasmer, MeteringPointsExhausted, Trap);
+create_exception!(wasmer, Interrupted, Trap);
+create_exception!(wasmer, MemoryError, RuntimeError);
+create_exception!(wasmer, DeserializeError, RuntimeError);
+
+/// Builds a `PyErr` of type `PyError`, and attaches the given
+/// attributes to the exception instance, so that the structured data
+/// of the original error is preserved on the Python side.
+pub(crate) fn to_py_err_with_attributes<PyError, Message>(
+    py: Python,
+    message: Message,
+    attributes: &[(&str, PyObject)],
+) -> PyErr
+where
+    PyError: PyTypeObject,
+    Message: ToString,
+{
+    let error = to_py_err::<PyError, _>(message);
+
+    if attributes.is_empty() {
+        return error;
+    }
+
+    let instance = error.instance(py);
+
+    for (name, value) in attributes {
+        if let Err(error) = instance.setattr(*name, value) {
+            return error;
+        }
+    }
+
+    PyErr::from_instance(instance)
+}
+
+pub(crate) fn compile_error_to_py_err(py: Python, error: wasmer::CompileError) -> PyErr {
+    let message = error.to_string();
+
+    match error {
+        wasmer::CompileError::Wasm(wasmer::WasmError::InvalidWebAssembly {
+            message: reason,
+            offset,
+        }) => to_py_err_with_attributes::<ValidationError, _>(
+            py,
+            message,
+            &[
+                ("reason", reason.to_object(py)),
+                ("offset", offset.to_object(py)),
+                ("section", py.None()),
+            ],
+        ),
+        wasmer::CompileError::Validate(reason) => to_py_err_with_attributes::<ValidationError, _>(
+            py,
+            message,
+            &[
+                ("reason", reason.to_object(py)),
+                ("offset", py.None()),
+                ("section", py.None()),
+            ],
+        ),
+        _ => to_py_err::<CompileError, _>(message),
+    }
+}
+
+pub(crate) fn instantiation_error_to_py_err(
+    py: Python,
+    error: wasmer::InstantiationError,
+) -> PyErr {
+    match error {
+        wasmer::InstantiationError::Link(error) => link_error_to_py_err(py, error),
+        wasmer::InstantiationError::Start(error) => runtime_error_to_py_err(py, error),
+    }
+}
+
+pub(crate) fn link_error_to_py_err(py: Python, error: wasmer::LinkError) -> PyErr {
+    let message = error.to_string();
+
+    match error {
+        wasmer::LinkError::Import(module, name, _) => to_py_err_with_attributes::<LinkError, _>(
+            py,
+            message,
+            &[
+                ("module", module.to_object(py)),
+                ("name", name.to_object(py)),
+            ],
+        ),
+        wasmer::LinkError::Trap(error) => runtime_error_to_py_err(py, error),
+        wasmer::LinkError::Resource(_) => to_py_err_with_attributes::<LinkError, _>(
+            py,
+            message,
+            &[("module", py.None()), ("name", py.None())],
+        ),
+    }
+}
+
+/// A Python exception raised by a host function.
+///
+/// It is stored inside a `wasmer::RuntimeError` when the exception
+/// unwinds through WebAssembly code, so that it can be re-raised
+/// unchanged (with its traceback and `__cause__`) on the other side,
+/// see `runtime_error_to_py_err`.
+pub(crate) struct HostError(PyErr);
+
+impl fmt::Debug for HostError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("HostError")
+    }
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("A Python exception has been raised by a host function")
+    }
+}
+
+impl Error for HostError {}
+
+pub(crate) fn py_err_to_runtime_error(error: PyErr) -> wasmer::RuntimeError {
+    wasmer::RuntimeError::user(Box::new(HostError(error)))
+}
+
+pub(crate) fn runtime_error_to_py_err(py: Python, error: wasmer::RuntimeError) -> PyErr {
+    instance_runtime_error_to_py_err(py, error, false, None)
+}
+
+/// Like `runtime_error_to_py_err`, but raises `Interrupted` instead
+/// of `Trap` if the call has been interrupted, or
+/// `MeteringPointsExhausted` if the error has been caused by the
+/// exhaustion of the metering points of the instance.
+pub(crate) fn instance_runtime_error_to_py_err(
+    py: Python,
+    error: wasmer::RuntimeError,
+    interrupted: bool,
+    metering: Option<&Metering>,
+) -> PyErr {
+    // The error comes from a Python exception raised by a host
+    // function: re-raise it as is.
+    let error = match error.downcast::<HostError>() {
+        Ok(HostError(error)) => return error,
+        Err(error) => error,
+    };
+
+    let message = error.message();
+
+    let frames = match error
+        .trace()
+        .iter()
+        .map(|frame| Py::new(py, FrameInfo::from(frame)))
+        .collect::<PyResult<Vec<_>>>()
+    {
+        Ok(frames) => frames,
+        Err(error) => return error,
+    };
+    let code = error.to_trap().map(TrapCode::from);
+    let attributes = [
+        ("message", message.to_object(py)),
+        ("code", code.to_object(py)),
+        ("frames", frames.to_object(py)),
+    ];
+
+    if interrupted {
+        return to_py_err_with_attributes::<Interrupted, _>(py, &message, &attributes);
+    }
+
+    match metering {
+        Some(metering) if metering.is_exhausted() => {
+            to_py_err_with_attributes::<MeteringPointsExhausted, _>(py, &message, &attributes)
+        }
+
+        _ => to_py_err_with_attributes::<Trap, _>(py, &message, &attributes),
+    }
+}
+
+pub(crate) fn memory_error_to_py_err(py: Python, error: wasmer::MemoryError) -> PyErr {
+    let message = error.to_string();
+
+    match error {
+        wasmer::MemoryError::CouldNotGrow {
+            current,
+            attempted_delta,
+        } => to_py_err_with_attributes::<MemoryError, _>(
+            py,
+            message,
+            &[
+                ("current", current.0.to_object(py)),
+                ("attempted_delta", attempted_delta.0.to_object(py)),
+            ],
+        ),
+        _ => to_py_err::<MemoryError, _>(message),
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
     errors::to_py_err,
     externals::{Function, Global, Memory, Table},
-    wasmer_inner::wasmer,
+    interruption::Interruption,
+    metering::Metering,
+    names::ParameterNames,
+    tunables::InstanceSlot,
+    wasmer_inner::{wasmer, wasmer_engines::START_FUNCTION},
 };
 use pyo3::{
     class::{basic::PyObjectProtocol, sequence::PySequenceProtocol},
     exceptions::LookupError,
     prelude::*,
 };
+use std::sync::Arc;
 
 /// Represents all the exports of an instance. It is built by
 /// `Instance.exports`.
@@ -38,15 +43,74 @@ use pyo3::{
 /// assert isinstance(exports.tab, Table)
 /// assert isinstance(exports.mem, Memory)
 /// ```
-#[pyclass(unsendable)]
+#[pyclass]
 #[derive(Clone)]
 pub struct Exports {
-    inner: wasmer::Exports,
+    inner: Arc<ExportsInner>,
+}
+
+/// The exports of an instance, shared by the `Exports` objects and the
+/// functions they give.
+pub(crate) struct ExportsInner {
+    exports: wasmer::Exports,
+    parameter_names: Arc<ParameterNames>,
+    interruption: Option<Interruption>,
+    metering: Option<Metering>,
+
+    /// The slot of the instance in the instance limit of its store,
+    /// released when the exports are dropped, i.e. when nothing from
+    /// the instance is alive anymore: each object built from the
+    /// exports holds them.
+    #[allow(unused)]
+    slot: Option<InstanceSlot>,
+}
+
+impl ExportsInner {
+    pub(crate) fn interruption(&self) -> Option<&Interruption> {
+        self.interruption.as_ref()
+    }
+
+    pub(crate) fn metering(&self) -> Option<&Metering> {
+        self.metering.as_ref()
+    }
 }
 
 impl Exports {
-    pub fn new(inner: wasmer::Exports) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: &wasmer::Exports,
+        parameter_names: Arc<ParameterNames>,
+        metering: Option<Metering>,
+        slot: Option<InstanceSlot>,
+    ) -> Self {
+        // The deferred start function is called once by the instance,
+        // it must not be called again.
+        let exports = inner
+            .iter()
+            .filter(|(name, _)| name.as_str() != START_FUNCTION)
+            .map(|(name, export)| (name.clone(), export.clone()))
+            .collect();
+
+        Self {
+            inner: Arc::new(ExportsInner {
+                exports,
+                parameter_names,
+                interruption: Interruption::new(inner),
+                metering,
+                slot,
+            }),
+        }
+    }
+
+    pub(crate) fn inner(&self) -> &wasmer::Exports {
+        &self.inner.exports
+    }
+
+    pub(crate) fn shared(&self) -> &Arc<ExportsInner> {
+        &self.inner
+    }
+
+    pub(crate) fn interruption(&self) -> Option<&Interruption> {
+        self.inner.interruption()
     }
 }
 
@@ -56,19 +120,31 @@ impl PyObjectProtocol for Exports {
         let gil_guard = Python::acquire_gil();
         let py = gil_guard.python();
 
-        Ok(match self.inner.get_extern(key.as_str()) {
-            Some(wasmer::Extern::Function(function)) => {
-                Py::new(py, Function::raw_new(function.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Global(global)) => {
-                Py::new(py, Global::raw_new(global.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Memory(memory)) => {
-                Py::new(py, Memory::raw_new(memory.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Table(table)) => {
-                Py::new(py, Table::raw_new(table.clone()))?.to_object(py)
-            }
+        Ok(match self.inner.exports.get_extern(key.as_str()) {
+            Some(wasmer::Extern::Function(function)) => Py::new(
+                py,
+                Function::raw_with_parameter_names(
+                    function.clone(),
+                    self.inner.parameter_names.get(key.as_str()).cloned(),
+                )
+                .with_exports(self.inner.clone()),
+            )?
+            .to_object(py),
+            Some(wasmer::Extern::Global(global)) => Py::new(
+                py,
+                Global::raw_new(global.clone()).with_exports(self.inner.clone()),
+            )?
+            .to_object(py),
+            Some(wasmer::Extern::Memory(memory)) => Py::new(
+                py,
+                Memory::raw_new(memory.clone()).with_exports(self.inner.clone()),
+            )?
+            .to_object(py),
+            Some(wasmer::Extern::Table(table)) => Py::new(
+                py,
+                Table::raw_new(table.clone()).with_exports(self.inner.clone()),
+            )?
+            .to_object(py),
             _ => {
                 return Err(to_py_err::<LookupError, _>(format!(
                     "Export `{}` does not exist.",
@@ -82,6 +158,6 @@ impl PyObjectProtocol for Exports {
 #[pyproto]
 impl PySequenceProtocol for Exports {
     fn __len__(&self) -> usize {
-        self.inner.len()
+        self.inner.exports.len()
     }
 }
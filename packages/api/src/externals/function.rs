@@ -1,16 +1,20 @@
 use crate::{
-    errors::to_py_err,
+    errors::{py_err_to_runtime_error, to_py_err},
+    exports::{Exports, ExportsInner},
+    externals::{Memory, NativeFunction},
+    interruption::call_function,
+    metering::Metering,
     store::Store,
-    types::FunctionType,
-    values::{to_py_object, to_wasm_value},
+    types::{FunctionType, Type},
+    values::{to_py_object, to_py_object_from, to_py_value, to_wasm_value},
     wasmer_inner::wasmer,
 };
 use pyo3::{
-    exceptions::{RuntimeError, ValueError},
+    exceptions::{LookupError, RuntimeError, TypeError, ValueError},
     prelude::*,
-    types::{PyDict, PyTuple},
+    types::{PyDict, PyString, PyTuple},
 };
-use std::io;
+use std::{cell::RefCell, sync::Arc, time::Duration};
 
 /// Represents a WebAssembly function instance.
 ///
@@ -45,6 +49,27 @@ use std::io;
 /// function = Function(store, sum)
 /// ```
 ///
+/// `int` maps to `i32` and `float` maps to `f32`. To be explicit, use
+/// the `wasmer.i32`, `wasmer.i64`, `wasmer.f32`, `wasmer.f64`,
+/// `wasmer.v128`, `wasmer.externref` and `wasmer.funcref` marker
+/// types, or the `wasmer.Type` members directly. `typing.NewType`
+/// aliases of them are supported too, and multiple results are
+/// expressed with `typing.Tuple`:
+///
+/// ```py
+/// from typing import Tuple
+/// from wasmer import Store, Function, Type, i64, f64
+///
+/// def swap(x: i64, y: Type.F64) -> Tuple[f64, i64]:
+///     return (y, x)
+///
+/// store = Store()
+/// function = Function(store, swap)
+///
+/// assert function.type.params == [Type.I64, Type.F64]
+/// assert function.type.results == [Type.F64, Type.I64]
+/// ```
+///
 /// Second, the same code but without annotations and a `FunctionType`:
 ///
 /// ```py
@@ -56,39 +81,372 @@ use std::io;
 /// store = Store()
 /// function = Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
 /// ```
-#[pyclass(unsendable)]
-#[text_signature = "(store, function, function_type)"]
+///
+/// If a host function raises an exception, it unwinds through the
+/// WebAssembly code, and it is re-raised unchanged to the caller of
+/// the exported function:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function
+///
+/// def fail() -> int:
+///     raise KeyError('oops')
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "fail" (func $fail (result i32)))
+///       (func (export "run") (result i32)
+///         call $fail))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"fail": Function(store, fail)})
+///
+/// instance = Instance(module, import_object)
+///
+/// try:
+///     instance.exports.run()
+/// except KeyError as error:
+///     assert error.args == ('oops',)
+/// ```
+///
+/// A host function can access the instance that calls it, e.g. to
+/// read strings from its memory, by opting in with `with_env=True`,
+/// or by passing any Python object with `env=…`. The function
+/// receives a `CallerContext` object as its first argument:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function, FunctionType, Type
+///
+/// def log(context, pointer, length):
+///     memory = context.memory.uint8_view(offset=pointer)
+///     context.data.append(bytes(memory[0:length]).decode())
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "log" (func $log (param i32 i32)))
+///       (memory (export "memory") 1)
+///       (data (i32.const 0) "Hello")
+///       (func (export "run")
+///         i32.const 0
+///         i32.const 5
+///         call $log))
+///     """
+/// )
+///
+/// logs = []
+///
+/// import_object = ImportObject()
+/// import_object.register(
+///     "env",
+///     {
+///         "log": Function(store, log, FunctionType([Type.I32, Type.I32], []), env=logs)
+///     }
+/// )
+///
+/// instance = Instance(module, import_object)
+/// instance.exports.run()
+///
+/// assert logs == ['Hello']
+/// ```
+///
+/// A host function can be asynchronous, i.e. an `async def`
+/// function. The guest is suspended while the coroutine runs. When
+/// the guest is called with `Function.call_async`, the coroutine runs
+/// on the event loop of the caller, so that it can do some
+/// asynchronous I/O; otherwise it runs on a new event loop. Calling
+/// the guest synchronously from a coroutine, i.e. while an event loop
+/// is running in the same thread, raises a `RuntimeError`.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function
+/// import asyncio
+///
+/// async def fetch(x: int) -> int:
+///     await asyncio.sleep(0.01)
+///     return x * 2
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "fetch" (func $fetch (param i32) (result i32)))
+///       (func (export "run") (param i32) (result i32)
+///         local.get 0
+///         call $fetch))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"fetch": Function(store, fetch)})
+///
+/// instance = Instance(module, import_object)
+///
+/// async def main():
+///     return await instance.exports.run.call_async(21)
+///
+/// assert asyncio.get_event_loop().run_until_complete(main()) == 42
+/// ```
+///
+/// With `raw=True`, the host function receives its arguments as
+/// `Value` objects instead of Python numbers, see `Value`. It can
+/// return `Value` objects in any mode.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function, Value
+///
+/// received = []
+///
+/// def host(x: float) -> float:
+///     received.append(x)
+///
+///     return x
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "host" (func $host (param f32) (result f32)))
+///       (func (export "run") (param f32) (result f32)
+///         local.get 0
+///         call $host))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"host": Function(store, host, raw=True)})
+///
+/// instance = Instance(module, import_object)
+/// result = instance.exports.run.call((Value.f32_from_bits(0x7fc00001),), raw=True)
+///
+/// assert received == [Value.f32_from_bits(0x7fc00001)]
+/// assert result.bits == 0x7fc00001
+/// ```
+#[pyclass]
+#[text_signature = "(store, function, function_type=None, env=None, with_env=False, raw=False)"]
 pub struct Function {
     inner: wasmer::Function,
+    parameter_names: Option<Vec<Option<String>>>,
+    exports: Option<Arc<ExportsInner>>,
 }
 
 impl Function {
     pub fn raw_new(inner: wasmer::Function) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            parameter_names: None,
+            exports: None,
+        }
+    }
+
+    pub fn raw_with_parameter_names(
+        inner: wasmer::Function,
+        parameter_names: Option<Vec<Option<String>>>,
+    ) -> Self {
+        Self {
+            parameter_names,
+            ..Self::raw_new(inner)
+        }
+    }
+
+    /// Attaches the exports of the instance the function belongs to,
+    /// so that the exhaustion of the metering points can be reported,
+    /// and so that the caller contexts of the instance stay bound as
+    /// long as the function is alive.
+    pub(crate) fn with_exports(self, exports: Arc<ExportsInner>) -> Self {
+        Self {
+            exports: Some(exports),
+            ..self
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Function {
         &self.inner
     }
+
+    /// Maps the positional and keyword arguments to the parameters of
+    /// the function, and converts them to WebAssembly values. The
+    /// arity and the type of each argument are checked.
+    fn arguments(
+        &self,
+        arguments: &PyTuple,
+        keyword_arguments: Option<&PyDict>,
+    ) -> PyResult<Vec<wasmer::Value>> {
+        let function_type = self.inner.ty();
+        let params = function_type.params();
+
+        if arguments.len() > params.len() {
+            return Err(to_py_err::<TypeError, _>(format!(
+                "Function takes {} argument(s) but {} were given",
+                params.len(),
+                arguments.len()
+            )));
+        }
+
+        let mut slots: Vec<Option<&PyAny>> = vec![None; params.len()];
+
+        for (slot, argument) in slots.iter_mut().zip(arguments.iter()) {
+            *slot = Some(argument);
+        }
+
+        if let Some(keyword_arguments) = keyword_arguments {
+            for (name, argument) in keyword_arguments {
+                let name = name.extract::<String>()?;
+                let index = self
+                    .parameter_names
+                    .iter()
+                    .flatten()
+                    .position(|parameter_name| parameter_name.as_ref() == Some(&name))
+                    .ok_or_else(|| {
+                        to_py_err::<TypeError, _>(format!(
+                            "Function got an unexpected keyword argument `{}`",
+                            name
+                        ))
+                    })?;
+
+                if slots[index].is_some() {
+                    return Err(to_py_err::<TypeError, _>(format!(
+                        "Function got multiple values for argument `{}`",
+                        name
+                    )));
+                }
+
+                slots[index] = Some(argument);
+            }
+        }
+
+        slots
+            .into_iter()
+            .zip(params.iter().cloned())
+            .enumerate()
+            .map(|(index, (argument, ty))| {
+                let expected_type = <&'static str>::from(&Type::from(&ty));
+
+                let argument = argument.ok_or_else(|| {
+                    to_py_err::<TypeError, _>(format!(
+                        "Function is missing argument #{} of type `Type.{}`",
+                        index, expected_type,
+                    ))
+                })?;
+
+                // Other errors, e.g. an `OverflowError` for an integer
+                // out of range, are more precise: keep them.
+                to_wasm_value((argument, ty)).map_err(|error| {
+                    if !error.is_instance::<TypeError>(argument.py()) {
+                        return error;
+                    }
+
+                    to_py_err::<TypeError, _>(format!(
+                        "Argument #{} must be of type `Type.{}`, given `{}`",
+                        index,
+                        expected_type,
+                        argument.get_type().name(),
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Calls the function, see `__call__` and `call`.
+    fn call_with(
+        &self,
+        py: Python,
+        arguments: &PyTuple,
+        keyword_arguments: Option<&PyDict>,
+        timeout: Option<Duration>,
+        raw: bool,
+    ) -> PyResult<PyObject> {
+        let arguments = self.arguments(arguments, keyword_arguments)?;
+        let function = &self.inner;
+
+        let results = call_function(py, self.exports.as_deref(), timeout, || {
+            function.call(&arguments).map(<[_]>::into_vec)
+        })?;
+
+        let to_py_object = to_py_object_from(py, self.exports.clone());
+        let convert = |value: &wasmer::Value| {
+            if raw {
+                to_py_value(py)(value)
+            } else {
+                to_py_object(value)
+            }
+        };
+
+        Ok(match results.len() {
+            0 => py.None(),
+            1 => convert(&results[0])?,
+            _ => PyTuple::new(
+                py,
+                results
+                    .iter()
+                    .map(convert)
+                    .collect::<PyResult<Vec<PyObject>>>()?,
+            )
+            .to_object(py),
+        })
+    }
 }
 
 #[pymethods]
 impl Function {
     #[new]
+    #[args(function_type = "None", env = "None", with_env = false, raw = false)]
     fn new(
         py: Python,
         store: &Store,
         py_function: &PyAny,
         function_type: Option<&FunctionType>,
+        env: Option<PyObject>,
+        with_env: bool,
+        raw: bool,
     ) -> PyResult<Self> {
         if !py_function.is_callable() {
             return Err(to_py_err::<ValueError, _>("Function must be a callable"));
         }
 
+        let context = if with_env || env.is_some() {
+            Some(ContextBinding::new(env))
+        } else {
+            None
+        };
+
+        // The parameters that receive the WebAssembly arguments, in
+        // order. The caller context, if any, is not part of them.
+        let parameters = match signature_parameters(py, py_function)? {
+            Some(parameters) if context.is_some() => match parameters.split_first() {
+                Some((_, parameters)) => Some(parameters.to_vec()),
+                None => {
+                    return Err(to_py_err::<ValueError, _>(
+                        "The function must receive the caller context as its first parameter",
+                    ))
+                }
+            },
+            parameters => parameters,
+        };
+
         let (argument_types, result_types) = match function_type {
             Some(function_type) => {
                 let function_type: wasmer::FunctionType = function_type.into();
 
+                if let Some(parameters) = &parameters {
+                    if parameters.len() != function_type.params().len() {
+                        return Err(to_py_err::<ValueError, _>(format!(
+                            "The function has {} parameter(s), but its type expects {}",
+                            parameters.len(),
+                            function_type.params().len(),
+                        )));
+                    }
+                }
+
                 (
                     function_type.params().to_vec(),
                     function_type.results().to_vec(),
@@ -96,50 +454,45 @@ impl Function {
             }
 
             None => {
-                if !py_function.hasattr("__annotations__")? {
-                    return Err(to_py_err::<ValueError, _>(
-                        "The function must have type annotations",
-                    ));
-                }
+                let parameters = parameters.ok_or_else(|| {
+                    to_py_err::<ValueError, _>(
+                        "The function signature cannot be inspected; a `FunctionType` must be given",
+                    )
+                })?;
+                let annotations = type_hints(py, py_function)?;
 
-                let annotations = py_function
-                    .getattr("__annotations__")?
-                    .downcast::<PyDict>()
-                    .map_err(PyErr::from)?;
-
-                let mut argument_types = Vec::new();
-                let mut result_types = Vec::new();
-
-                for (annotation_name, annotation_value) in annotations {
-                    let ty = match annotation_value.to_string().as_str() {
-                        "i32" | "I32" | "<class 'int'>" => wasmer::Type::I32,
-                        "i64" | "I64" => wasmer::Type::I64,
-                        "f32" | "F32" | "<class 'float'>" => wasmer::Type::F32,
-                        "f64" | "F64" => wasmer::Type::F64,
-                        ty => {
-                            return Err(to_py_err::<RuntimeError, _>(format!(
-                                "Type `{}` is not a supported type",
-                                ty,
-                            )))
-                        }
-                    };
+                let argument_types = parameters
+                    .iter()
+                    .map(|parameter| match annotations.get_item(parameter.as_str()) {
+                        Some(annotation) => annotation_to_type(py, annotation),
+                        None => Err(to_py_err::<ValueError, _>(format!(
+                            "The parameter `{}` must have a type annotation",
+                            parameter
+                        ))),
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
 
-                    match annotation_name.to_string().as_str() {
-                        "return" => result_types.push(ty),
-                        _ => argument_types.push(ty),
-                    }
-                }
+                let result_types = match annotations.get_item("return") {
+                    Some(annotation) => annotation_to_result_types(py, annotation)?,
+                    None => Vec::new(),
+                };
 
                 (argument_types, result_types)
             }
         };
 
-        struct Environment {
-            py_function: PyObject,
-        }
+        // Check once whether the host function is asynchronous, rather
+        // than on every call.
+        let is_async = py
+            .import("inspect")?
+            .call1("iscoroutinefunction", (py_function,))?
+            .is_true()?;
 
         let environment = Environment {
-            py_function: py_function.to_object(py),
+            py_function: Arc::new(py_function.to_object(py)),
+            is_async,
+            raw,
+            context,
         };
 
         let host_function = wasmer::Function::new_with_env(
@@ -152,15 +505,32 @@ impl Function {
                 let gil = Python::acquire_gil();
                 let py = gil.python();
 
-                let to_py_object = to_py_object(py);
-                let arguments: Vec<PyObject> = arguments.iter().map(to_py_object).collect();
+                let convert = |value: &wasmer::Value| {
+                    if environment.raw {
+                        to_py_value(py)(value)
+                    } else {
+                        to_py_object(py)(value)
+                    }
+                };
+                let arguments: Vec<PyObject> = environment
+                    .context
+                    .iter()
+                    .map(|context| Ok(Py::new(py, context.caller_context(py))?.to_object(py)))
+                    .chain(arguments.iter().map(convert))
+                    .collect::<PyResult<_>>()
+                    .map_err(py_err_to_runtime_error)?;
 
                 let results = environment
                     .py_function
                     .call(py, PyTuple::new(py, arguments), None)
-                    .map_err(|error| {
-                        wasmer::RuntimeError::new(io::Error::from(error).to_string())
-                    })?;
+                    .and_then(|results| {
+                        if environment.is_async {
+                            run_coroutine(py, results)
+                        } else {
+                            Ok(results)
+                        }
+                    })
+                    .map_err(py_err_to_runtime_error)?;
 
                 let result_types = result_types.clone();
 
@@ -170,22 +540,16 @@ impl Function {
                         .zip(result_types)
                         .map(to_wasm_value)
                         .collect::<PyResult<_>>()
-                        .map_err(|error| {
-                            wasmer::RuntimeError::new(io::Error::from(error).to_string())
-                        })?
+                        .map_err(py_err_to_runtime_error)?
                 } else if !results.is_none(py) && !result_types.is_empty() {
                     vec![to_wasm_value((
                         results
                             .cast_as::<PyAny>(py)
                             .map_err(PyErr::from)
-                            .map_err(|error| {
-                                wasmer::RuntimeError::new(io::Error::from(error).to_string())
-                            })?,
+                            .map_err(py_err_to_runtime_error)?,
                         result_types[0],
                     ))
-                    .map_err(|error| {
-                        wasmer::RuntimeError::new(io::Error::from(error).to_string())
-                    })?]
+                    .map_err(py_err_to_runtime_error)?]
                 } else {
                     Vec::new()
                 })
@@ -196,32 +560,233 @@ impl Function {
     }
 
     /// Calls the function as a regular Python function.
+    ///
+    /// The number of arguments and their types are checked against
+    /// the function type. If the module has a name section defining
+    /// the parameter names, the arguments can also be passed by
+    /// keyword.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sub") (param $x i32) (param $y i32) (result i32)
+    ///         local.get $x
+    ///         local.get $y
+    ///         i32.sub))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert instance.exports.sub(3, 2) == 1
+    /// assert instance.exports.sub(3, y=2) == 1
+    /// assert instance.exports.sub(y=2, x=3) == 1
+    /// ```
+    ///
+    /// The GIL is released while the WebAssembly code runs, so that
+    /// other Python threads are not blocked by a long-running call.
+    ///
+    /// The arguments can be `Value` objects too, see `Value`. To get
+    /// the results as `Value` objects, see `call`.
     #[call]
-    #[args(arguments = "*")]
-    fn __call__<'p>(&self, py: Python<'p>, arguments: &PyTuple) -> PyResult<PyObject> {
-        let arguments: Vec<wasmer::Value> = arguments
-            .iter()
-            .zip(self.inner.ty().params().iter().cloned())
-            .map(to_wasm_value)
-            .collect::<PyResult<_>>()?;
-
-        let results = self
-            .inner
-            .call(&arguments)
-            .map(<[_]>::into_vec)
-            .map_err(to_py_err::<RuntimeError, _>)?;
-
-        let to_py_object = to_py_object(py);
+    #[args(arguments = "*", keyword_arguments = "**")]
+    fn __call__<'p>(
+        &self,
+        py: Python<'p>,
+        arguments: &PyTuple,
+        keyword_arguments: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        self.call_with(py, arguments, keyword_arguments, None, false)
+    }
 
-        Ok(match results.len() {
-            0 => py.None(),
-            1 => to_py_object(&results[0]),
-            _ => PyTuple::new(
-                py,
-                results.iter().map(to_py_object).collect::<Vec<PyObject>>(),
-            )
-            .to_object(py),
-        })
+    /// Calls the function like `__call__`, with the arguments given
+    /// as a sequence and a dictionary, so that they never clash with
+    /// the options of the call.
+    ///
+    /// The `timeout` option, in seconds, interrupts the call if it
+    /// runs for too long, by raising a `wasmer.errors.Interrupted`
+    /// exception. The function must be exported by an instance whose
+    /// module has been compiled by an engine with a compiler. The
+    /// timeout only interrupts this call: while it runs, the other
+    /// calls to the same instance wait for it, and it waits for them
+    /// to return before starting, see `InterruptHandle`.
+    ///
+    /// The `raw` option, if true, returns the results as `Value`
+    /// objects instead of Python numbers, see `Value`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import errors, Store, Module, Instance, Value
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sub") (param $x i32) (param $y i32) (result i32)
+    ///         local.get $x
+    ///         local.get $y
+    ///         i32.sub)
+    ///       (func (export "loop")
+    ///         (loop br 0)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert instance.exports.sub.call((3,), {'y': 2}, timeout=1) == 1
+    /// assert instance.exports.sub.call((3, 2), raw=True) == Value.i32(1)
+    ///
+    /// try:
+    ///     instance.exports.loop.call(timeout=0.1)
+    /// except errors.Interrupted:
+    ///     pass
+    /// ```
+    #[text_signature = "($self, arguments=(), keyword_arguments=None, timeout=None, raw=False)"]
+    #[args(
+        arguments = "None",
+        keyword_arguments = "None",
+        timeout = "None",
+        raw = false
+    )]
+    fn call(
+        &self,
+        py: Python,
+        arguments: Option<Vec<&PyAny>>,
+        keyword_arguments: Option<&PyDict>,
+        timeout: Option<f64>,
+        raw: bool,
+    ) -> PyResult<PyObject> {
+        let timeout = match timeout {
+            Some(timeout) if !timeout.is_finite() || timeout < 0.0 => {
+                return Err(to_py_err::<ValueError, _>(
+                    "The timeout must be a positive number of seconds",
+                ))
+            }
+
+            Some(timeout) => Some(Duration::from_secs_f64(timeout)),
+            None => None,
+        };
+
+        self.call_with(
+            py,
+            PyTuple::new(py, arguments.unwrap_or_default()),
+            keyword_arguments,
+            timeout,
+            raw,
+        )
+    }
+
+    /// Calls the function asynchronously. It returns an awaitable,
+    /// and runs the WebAssembly code in the default executor of the
+    /// current event loop, so that the event loop is not blocked.
+    ///
+    /// The asynchronous host functions called by the WebAssembly code
+    /// run on the current event loop.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    /// import asyncio
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (type (func (param i32 i32) (result i32)))
+    ///       (func (type 0)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add)
+    ///       (export "sum" (func 0)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// async def main():
+    ///     return await instance.exports.sum.call_async(1, 2)
+    ///
+    /// assert asyncio.get_event_loop().run_until_complete(main()) == 3
+    /// ```
+    #[text_signature = "($self, *arguments, **keyword_arguments)"]
+    #[args(arguments = "*", keyword_arguments = "**")]
+    fn call_async(
+        slf: PyRef<Self>,
+        py: Python,
+        arguments: &PyTuple,
+        keyword_arguments: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let event_loop = py.import("asyncio")?.call0("get_running_loop")?;
+        let call = AsyncCall {
+            function: slf.into_py(py),
+            event_loop: event_loop.to_object(py),
+            arguments: arguments.into(),
+            keyword_arguments: keyword_arguments.map(Into::into),
+        };
+
+        Ok(event_loop
+            .call_method1("run_in_executor", (py.None(), Py::new(py, call)?))?
+            .to_object(py))
+    }
+
+    /// Returns a `NativeFunction`, i.e. a specialised version of this
+    /// function for its static signature, which is faster to call
+    /// repeatedly. See `NativeFunction` to learn more.
+    ///
+    /// The parameter and result types default to the function type. If
+    /// they are given, they must match the function type, otherwise a
+    /// `TypeError` is raised.
+    ///
+    /// Only some signatures are supported: up to 3 parameters, and 0
+    /// or 1 result, of types `i32`, `i64`, `f32` or `f64`. A
+    /// `TypeError` is raised for any other signature; use the
+    /// function itself in this case.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Type
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (type (func (param i32 i32) (result i32)))
+    ///       (func (type 0)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add)
+    ///       (export "sum" (func 0)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// sum = instance.exports.sum.native([Type.I32, Type.I32], [Type.I32])
+    ///
+    /// assert sum(1, 2) == 3
+    /// ```
+    #[text_signature = "($self, /, params=None, results=None)"]
+    #[args(params = "None", results = "None")]
+    fn native(
+        &self,
+        params: Option<Vec<Type>>,
+        results: Option<Vec<Type>>,
+    ) -> PyResult<NativeFunction> {
+        let function_type = self.inner.ty();
+        let params = match params {
+            Some(params) => params.into_iter().map(Into::into).collect(),
+            None => function_type.params().to_vec(),
+        };
+        let results = match results {
+            Some(results) => results.into_iter().map(Into::into).collect(),
+            None => function_type.results().to_vec(),
+        };
+
+        NativeFunction::raw_new(&self.inner, &params, &results, self.exports.clone())
     }
 
     /// Returns the type of the function as a `FunctionType` object.
@@ -256,3 +821,387 @@ impl Function {
         self.inner.ty().into()
     }
 }
+
+/// The context of a host function, i.e. a `Function` created with
+/// `env=…` or `with_env=True`. It is given as the first argument of
+/// the host function every time it is called.
+///
+/// Each `Instance` importing the host function has its own context,
+/// which gives access to the exports of this instance (the caller).
+/// The context is bound when the instance is created, before its
+/// start function runs, so the start function can use it too. The
+/// functions from `CallerContext.exports` take positional arguments
+/// only.
+///
+/// See the `Function` class to see an example.
+#[pyclass]
+pub struct CallerContext {
+    /// The Python object given to `Function` with `env=…`, or `None`.
+    #[pyo3(get)]
+    data: Option<PyObject>,
+
+    exports: Option<Exports>,
+}
+
+impl CallerContext {
+    fn caller_exports(&self) -> PyResult<Exports> {
+        self.exports.clone().ok_or_else(|| {
+            to_py_err::<RuntimeError, _>(
+                "The caller context is not initialized yet; the host function must be imported by an instance first",
+            )
+        })
+    }
+}
+
+#[pymethods]
+impl CallerContext {
+    /// The exports of the caller instance, as an object of kind
+    /// `Exports`.
+    #[getter]
+    fn exports(&self) -> PyResult<Exports> {
+        self.caller_exports()
+    }
+
+    /// The memory exported by the caller instance, as an object of
+    /// kind `Memory`. If several memories are exported, the one named
+    /// `memory` is preferred.
+    #[getter]
+    fn memory(&self) -> PyResult<Memory> {
+        let exports = self.caller_exports()?;
+
+        exports
+            .inner()
+            .get_memory("memory")
+            .ok()
+            .or_else(|| {
+                exports.inner().iter().find_map(|(_, export)| match export {
+                    wasmer::Extern::Memory(memory) => Some(memory),
+                    _ => None,
+                })
+            })
+            .map(|memory| Memory::raw_new(memory.clone()))
+            .ok_or_else(|| {
+                to_py_err::<LookupError, _>("The caller instance does not export any memory")
+            })
+    }
+}
+
+/// The caller context of a host function, as stored in its
+/// environment, see `Environment`.
+#[derive(Clone)]
+struct ContextBinding {
+    data: Option<Arc<PyObject>>,
+
+    /// The exports of the instance importing the host function, set
+    /// when the environment is initialized with this instance.
+    exports: Option<Exports>,
+}
+
+impl ContextBinding {
+    fn new(data: Option<PyObject>) -> Self {
+        Self {
+            data: data.map(Arc::new),
+            exports: None,
+        }
+    }
+
+    fn caller_context(&self, py: Python) -> CallerContext {
+        CallerContext {
+            data: self.data.as_ref().map(|data| data.clone_ref(py)),
+            exports: self.exports.clone(),
+        }
+    }
+}
+
+/// The environment of a host function.
+///
+/// Wasmer clones it for every instance importing the host function,
+/// and then initializes the clone with this instance: each instance
+/// gets its own caller context. The Python objects are shared by the
+/// clones, so that cloning does not need the GIL.
+#[derive(Clone)]
+struct Environment {
+    py_function: Arc<PyObject>,
+    is_async: bool,
+    raw: bool,
+    context: Option<ContextBinding>,
+}
+
+impl wasmer::WasmerEnv for Environment {
+    fn init_with_instance(
+        &mut self,
+        instance: &wasmer::Instance,
+    ) -> Result<(), wasmer::HostEnvInitError> {
+        if let Some(context) = &mut self.context {
+            // The caller context does not need the parameter names:
+            // the functions from its exports are called with
+            // positional arguments only. It does not count the
+            // instance either, see `Limits`.
+            context.exports = Some(Exports::new(
+                &instance.exports,
+                Arc::default(),
+                Metering::new(&instance.exports),
+                None,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// The event loop of the `Function.call_async` call running in
+    /// the current thread, if any.
+    static EVENT_LOOP: RefCell<Option<PyObject>> = RefCell::new(None);
+}
+
+/// A call scheduled by `Function.call_async` in an executor thread.
+#[pyclass]
+struct AsyncCall {
+    function: PyObject,
+    event_loop: PyObject,
+    arguments: Py<PyTuple>,
+    keyword_arguments: Option<Py<PyDict>>,
+}
+
+#[pymethods]
+impl AsyncCall {
+    #[call]
+    fn __call__(&self, py: Python) -> PyResult<PyObject> {
+        let previous_event_loop =
+            EVENT_LOOP.with(|event_loop| event_loop.replace(Some(self.event_loop.clone_ref(py))));
+
+        let results = self.function.call(
+            py,
+            self.arguments.clone_ref(py),
+            self.keyword_arguments
+                .as_ref()
+                .map(|keyword_arguments| keyword_arguments.as_ref(py)),
+        );
+
+        EVENT_LOOP.with(|event_loop| event_loop.replace(previous_event_loop));
+
+        results
+    }
+}
+
+/// Runs the coroutine returned by an asynchronous host function to
+/// completion. The guest is suspended meanwhile.
+fn run_coroutine(py: Python, coroutine: PyObject) -> PyResult<PyObject> {
+    let asyncio = py.import("asyncio")?;
+    let event_loop = EVENT_LOOP.with(|event_loop| {
+        event_loop
+            .borrow()
+            .as_ref()
+            .map(|event_loop| event_loop.clone_ref(py))
+    });
+
+    match event_loop {
+        // The guest runs in an executor thread, scheduled by
+        // `Function.call_async`: run the coroutine on the event loop
+        // of the caller, and wait for it. Waiting releases the GIL.
+        Some(event_loop) => Ok(asyncio
+            .call1("run_coroutine_threadsafe", (coroutine, event_loop))?
+            .call_method0("result")?
+            .to_object(py)),
+
+        // The guest has been called synchronously from a coroutine:
+        // the running event loop cannot run another coroutine until
+        // the guest returns.
+        None if asyncio.call0("get_running_loop").is_ok() => {
+            // Close the coroutine, so that it is not reported as
+            // never awaited.
+            coroutine.call_method0(py, "close")?;
+
+            Err(to_py_err::<RuntimeError, _>(
+                "An asynchronous host function cannot run while an event loop is running in the same thread; call the guest with `Function.call_async`",
+            ))
+        }
+
+        // Otherwise, run the coroutine on a new event loop.
+        None => {
+            let event_loop = asyncio.call0("new_event_loop")?;
+            let results = event_loop.call_method1("run_until_complete", (coroutine,));
+            event_loop.call_method0("close")?;
+
+            Ok(results?.to_object(py))
+        }
+    }
+}
+
+/// Reads the names of the parameters of a Python function, in order,
+/// with `inspect.signature`.
+///
+/// Since WebAssembly passes all the arguments positionally,
+/// variadic and keyword-only parameters, and parameters with a
+/// default value, are rejected. It returns `None` if the signature
+/// cannot be inspected (e.g. for some built-in functions).
+fn signature_parameters(py: Python, py_function: &PyAny) -> PyResult<Option<Vec<String>>> {
+    let inspect_module = py.import("inspect")?;
+    let parameter_class = inspect_module.get("Parameter")?;
+    let positional_only = parameter_class.getattr("POSITIONAL_ONLY")?;
+    let positional_or_keyword = parameter_class.getattr("POSITIONAL_OR_KEYWORD")?;
+    let variadic_positional = parameter_class.getattr("VAR_POSITIONAL")?;
+    let variadic_keyword = parameter_class.getattr("VAR_KEYWORD")?;
+    let empty = parameter_class.getattr("empty")?;
+
+    let signature = match inspect_module.call1("signature", (py_function,)) {
+        Ok(signature) => signature,
+        Err(_) => return Ok(None),
+    };
+
+    signature
+        .getattr("parameters")?
+        .call_method0("values")?
+        .iter()?
+        .map(|parameter| {
+            let parameter = parameter?;
+            let name = parameter.getattr("name")?.extract::<String>()?;
+            let kind = parameter.getattr("kind")?;
+
+            if kind.as_ptr() == variadic_positional.as_ptr()
+                || kind.as_ptr() == variadic_keyword.as_ptr()
+            {
+                return Err(to_py_err::<ValueError, _>(format!(
+                    "The parameter `{}` cannot be variadic",
+                    name
+                )));
+            }
+
+            if kind.as_ptr() != positional_only.as_ptr()
+                && kind.as_ptr() != positional_or_keyword.as_ptr()
+            {
+                return Err(to_py_err::<ValueError, _>(format!(
+                    "The parameter `{}` cannot be keyword-only",
+                    name
+                )));
+            }
+
+            if parameter.getattr("default")?.as_ptr() != empty.as_ptr() {
+                return Err(to_py_err::<ValueError, _>(format!(
+                    "The parameter `{}` cannot have a default value",
+                    name
+                )));
+            }
+
+            Ok(name)
+        })
+        .collect::<PyResult<Vec<_>>>()
+        .map(Some)
+}
+
+/// Reads the type hints of a Python function, with
+/// `typing.get_type_hints` so that stringified annotations (e.g. with
+/// `from __future__ import annotations`) are resolved. It falls back
+/// to `__annotations__` if the type hints cannot be resolved.
+fn type_hints<'py>(py: Python<'py>, py_function: &'py PyAny) -> PyResult<&'py PyDict> {
+    if !py_function.hasattr("__annotations__")? {
+        return Err(to_py_err::<ValueError, _>(
+            "The function must have type annotations",
+        ));
+    }
+
+    py.import("typing")?
+        .call1("get_type_hints", (py_function,))
+        .or_else(|_| py_function.getattr("__annotations__"))?
+        .downcast::<PyDict>()
+        .map_err(PyErr::from)
+}
+
+/// Converts a Python annotation to a WebAssembly type.
+///
+/// Supported annotations are: members of `wasmer.Type`, the
+/// `wasmer.i32`, `wasmer.i64`, `wasmer.f32`, `wasmer.f64`,
+/// `wasmer.v128`, `wasmer.externref` and `wasmer.funcref` marker
+/// types, `int` (for `i32`), `float` (for `f32`), and any
+/// `typing.NewType` alias of them.
+fn annotation_to_type(py: Python, annotation: &PyAny) -> PyResult<wasmer::Type> {
+    let wasmer_module = py.import("wasmer")?;
+    let builtins_module = py.import("builtins")?;
+
+    // A member of `wasmer.Type`.
+    if builtins_module
+        .call1("isinstance", (annotation, wasmer_module.get("Type")?))?
+        .is_true()?
+    {
+        return Ok(annotation.extract::<Type>()?.into());
+    }
+
+    // A marker type, e.g. `wasmer.i32`.
+    for (marker, ty) in &[
+        ("i32", wasmer::Type::I32),
+        ("i64", wasmer::Type::I64),
+        ("f32", wasmer::Type::F32),
+        ("f64", wasmer::Type::F64),
+        ("v128", wasmer::Type::V128),
+        ("externref", wasmer::Type::ExternRef),
+        ("funcref", wasmer::Type::FuncRef),
+    ] {
+        if annotation.as_ptr() == wasmer_module.get(marker)?.as_ptr() {
+            return Ok(*ty);
+        }
+    }
+
+    // A `typing.NewType` alias.
+    if annotation.hasattr("__supertype__")? {
+        return annotation_to_type(py, annotation.getattr("__supertype__")?);
+    }
+
+    if annotation.as_ptr() == builtins_module.get("int")?.as_ptr() {
+        return Ok(wasmer::Type::I32);
+    }
+
+    if annotation.as_ptr() == builtins_module.get("float")?.as_ptr() {
+        return Ok(wasmer::Type::F32);
+    }
+
+    // An annotation that has not been resolved by
+    // `typing.get_type_hints`, e.g. `x: 'i32'`.
+    if let Ok(annotation) = annotation.downcast::<PyString>() {
+        match annotation.to_string()?.as_ref() {
+            "i32" | "I32" | "int" => return Ok(wasmer::Type::I32),
+            "i64" | "I64" => return Ok(wasmer::Type::I64),
+            "f32" | "F32" | "float" => return Ok(wasmer::Type::F32),
+            "f64" | "F64" => return Ok(wasmer::Type::F64),
+            "v128" | "V128" => return Ok(wasmer::Type::V128),
+            "externref" | "EXTERN_REF" => return Ok(wasmer::Type::ExternRef),
+            "funcref" | "FUNC_REF" => return Ok(wasmer::Type::FuncRef),
+            _ => (),
+        }
+    }
+
+    Err(to_py_err::<RuntimeError, _>(format!(
+        "Type `{}` is not a supported type",
+        annotation,
+    )))
+}
+
+/// Converts a Python return annotation to a list of WebAssembly
+/// types: `None` means no result, `typing.Tuple[…]` means multiple
+/// results, anything else is a single result.
+fn annotation_to_result_types(py: Python, annotation: &PyAny) -> PyResult<Vec<wasmer::Type>> {
+    let builtins_module = py.import("builtins")?;
+
+    // `None`, or `NoneType` once resolved by `typing.get_type_hints`.
+    if annotation.is_none()
+        || annotation.as_ptr() == builtins_module.call1("type", (py.None(),))?.as_ptr()
+    {
+        return Ok(Vec::new());
+    }
+
+    if annotation.hasattr("__origin__")? {
+        let origin = annotation.getattr("__origin__")?;
+
+        if origin.as_ptr() == builtins_module.get("tuple")?.as_ptr()
+            || origin.as_ptr() == py.import("typing")?.get("Tuple")?.as_ptr()
+        {
+            return annotation
+                .getattr("__args__")?
+                .iter()?
+                .map(|annotation| annotation_to_type(py, annotation?))
+                .collect();
+        }
+    }
+
+    Ok(vec![annotation_to_type(py, annotation)?])
+}
@@ -1,14 +1,16 @@
 use crate::{
     errors::to_py_err,
+    exports::ExportsInner,
     store::Store,
     types::GlobalType,
-    values::{to_py_object, to_wasm_value, Value},
+    values::{to_py_object_from, to_py_value, to_wasm_value, Value},
     wasmer_inner::wasmer,
 };
 use pyo3::{
     exceptions::{RuntimeError, ValueError},
     prelude::*,
 };
+use std::sync::Arc;
 
 /// Represents a WebAssembly global instance.
 ///
@@ -38,15 +40,33 @@ use pyo3::{
 ///
 /// assert global_.mutable == True
 /// ```
-#[pyclass(unsendable)]
+#[pyclass]
 #[text_signature = "(store, value, mutable)"]
 pub struct Global {
     inner: wasmer::Global,
+
+    /// The exports of the instance the global belongs to, if any, see
+    /// `Global::with_exports`. The function held by a `funcref`
+    /// global is attached to them too.
+    exports: Option<Arc<ExportsInner>>,
 }
 
 impl Global {
     pub fn raw_new(inner: wasmer::Global) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            exports: None,
+        }
+    }
+
+    /// Attaches the exports of the instance the global belongs to,
+    /// so that the instance stays counted by the instance limit of
+    /// its store as long as the global is alive, see `Limits`.
+    pub(crate) fn with_exports(self, exports: Arc<ExportsInner>) -> Self {
+        Self {
+            exports: Some(exports),
+            ..self
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Global {
@@ -61,12 +81,10 @@ impl Global {
         let store = store.inner();
         let value = value.inner().clone();
 
-        Self {
-            inner: match mutable {
-                Some(true) => wasmer::Global::new_mut(store, value),
-                _ => wasmer::Global::new(store, value),
-            },
-        }
+        Self::raw_new(match mutable {
+            Some(true) => wasmer::Global::new_mut(store, value),
+            _ => wasmer::Global::new(store, value),
+        })
     }
 
     /// Checks whether the global is mutable.
@@ -103,10 +121,31 @@ impl Global {
     /// assert global_.value == 153
     /// ```
     #[getter(value)]
-    fn get_value(&self, py: Python) -> PyObject {
-        let to_py_object = to_py_object(py);
+    fn get_value(&self, py: Python) -> PyResult<PyObject> {
+        to_py_object_from(py, self.exports.clone())(&self.inner.get())
+    }
 
-        to_py_object(&self.inner.get())
+    /// Like `Global.value`, but the value is returned as a `Value`
+    /// object, see `Value`. `Global.value` can be set with a `Value`
+    /// object too.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Global, Value
+    ///
+    /// store = Store()
+    /// global_ = Global(store, Value.f32_from_bits(0x7fc00001), mutable=True)
+    ///
+    /// assert global_.raw_value.bits == 0x7fc00001
+    ///
+    /// global_.value = Value.f32_from_bits(0x7fc00002)
+    ///
+    /// assert global_.raw_value == Value.f32_from_bits(0x7fc00002)
+    /// ```
+    #[getter]
+    fn raw_value(&self, py: Python) -> PyResult<PyObject> {
+        to_py_value(py)(&self.inner.get())
     }
 
     #[setter(value)]
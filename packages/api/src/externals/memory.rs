@@ -1,11 +1,16 @@
 use crate::{
-    errors::to_py_err,
-    memory::{Buffer, Int16Array, Int32Array, Int8Array, Uint16Array, Uint32Array, Uint8Array},
+    errors::memory_error_to_py_err,
+    exports::ExportsInner,
+    memory::{
+        Buffer, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        Uint16Array, Uint32Array, Uint64Array, Uint8Array,
+    },
     store::Store,
     types::MemoryType,
     wasmer_inner::wasmer,
 };
-use pyo3::{exceptions::RuntimeError, prelude::*};
+use pyo3::prelude::*;
+use std::sync::Arc;
 
 /// A WebAssembly memory instance.
 ///
@@ -49,15 +54,29 @@ use pyo3::{exceptions::RuntimeError, prelude::*};
 ///
 /// assert isinstance(memory, Memory)
 /// ```
-#[pyclass(unsendable)]
+#[pyclass]
 #[text_signature = "(store, memory_type)"]
 pub struct Memory {
     inner: wasmer::Memory,
+    exports: Option<Arc<ExportsInner>>,
 }
 
 impl Memory {
     pub fn raw_new(inner: wasmer::Memory) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            exports: None,
+        }
+    }
+
+    /// Attaches the exports of the instance the memory belongs to,
+    /// so that the instance stays counted by the instance limit of
+    /// its store as long as the memory is alive, see `Limits`.
+    pub(crate) fn with_exports(self, exports: Arc<ExportsInner>) -> Self {
+        Self {
+            exports: Some(exports),
+            ..self
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Memory {
@@ -68,10 +87,10 @@ impl Memory {
 #[pymethods]
 impl Memory {
     #[new]
-    fn new(store: &Store, memory_type: &MemoryType) -> PyResult<Self> {
+    fn new(py: Python, store: &Store, memory_type: &MemoryType) -> PyResult<Self> {
         Ok(Self::raw_new(
             wasmer::Memory::new(store.inner(), memory_type.into())
-                .map_err(to_py_err::<RuntimeError, _>)?,
+                .map_err(|error| memory_error_to_py_err(py, error))?,
         ))
     }
 
@@ -129,11 +148,11 @@ impl Memory {
     /// assert memory.size == 5
     /// ```
     #[text_signature = "($self, number_of_pages)"]
-    fn grow(&self, number_of_pages: u32) -> PyResult<u32> {
+    fn grow(&self, py: Python, number_of_pages: u32) -> PyResult<u32> {
         self.inner
             .grow(number_of_pages)
             .map(|pages| pages.0)
-            .map_err(to_py_err::<RuntimeError, _>)
+            .map_err(|error| memory_error_to_py_err(py, error))
     }
 
     /// Creates a Python buffer to read and write the memory data. See
@@ -152,7 +171,7 @@ impl Memory {
     /// ```
     #[getter]
     fn buffer(&self) -> Buffer {
-        Buffer::new(self.inner.clone())
+        Buffer::new(self.inner.clone(), self.exports.clone())
     }
 
     /// Creates a read-and-write view over the memory data where
@@ -176,6 +195,7 @@ impl Memory {
         Uint8Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -188,6 +208,7 @@ impl Memory {
         Int8Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -200,6 +221,7 @@ impl Memory {
         Uint16Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -212,6 +234,7 @@ impl Memory {
         Int16Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -224,6 +247,7 @@ impl Memory {
         Uint32Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -236,6 +260,59 @@ impl Memory {
         Int32Array {
             memory: self.inner.clone(),
             offset,
+            exports: self.exports.clone(),
+        }
+    }
+
+    /// Creates a read-and-write over the memory data where elements
+    /// are of kind `uint64`. See the `Uint64Array` view to learn
+    /// more, and the `Memory.uint8_view` method to see an example.
+    #[text_signature = "($self, /, offset=0)"]
+    #[args(offset = 0)]
+    fn uint64_view(&self, offset: usize) -> Uint64Array {
+        Uint64Array {
+            memory: self.inner.clone(),
+            offset,
+            exports: self.exports.clone(),
+        }
+    }
+
+    /// Creates a read-and-write over the memory data where elements
+    /// are of kind `int64`. See the `Int64Array` view to learn more,
+    /// and the `Memory.uint8_view` method to see an example.
+    #[text_signature = "($self, /, offset=0)"]
+    #[args(offset = 0)]
+    fn int64_view(&self, offset: usize) -> Int64Array {
+        Int64Array {
+            memory: self.inner.clone(),
+            offset,
+            exports: self.exports.clone(),
+        }
+    }
+
+    /// Creates a read-and-write over the memory data where elements
+    /// are of kind `float32`. See the `Float32Array` view to learn
+    /// more, and the `Memory.uint8_view` method to see an example.
+    #[text_signature = "($self, /, offset=0)"]
+    #[args(offset = 0)]
+    fn float32_view(&self, offset: usize) -> Float32Array {
+        Float32Array {
+            memory: self.inner.clone(),
+            offset,
+            exports: self.exports.clone(),
+        }
+    }
+
+    /// Creates a read-and-write over the memory data where elements
+    /// are of kind `float64`. See the `Float64Array` view to learn
+    /// more, and the `Memory.uint8_view` method to see an example.
+    #[text_signature = "($self, /, offset=0)"]
+    #[args(offset = 0)]
+    fn float64_view(&self, offset: usize) -> Float64Array {
+        Float64Array {
+            memory: self.inner.clone(),
+            offset,
+            exports: self.exports.clone(),
         }
     }
 
@@ -1,9 +1,11 @@
 mod function;
 mod global;
 mod memory;
+mod native_function;
 mod table;
 
-pub use function::Function;
+pub use function::{CallerContext, Function};
 pub use global::Global;
 pub use memory::Memory;
+pub use native_function::NativeFunction;
 pub use table::Table;
@@ -0,0 +1,183 @@
+use crate::{
+    errors::to_py_err, exports::ExportsInner, interruption::call_function, wasmer_inner::wasmer,
+};
+use pyo3::{exceptions::TypeError, prelude::*, types::PyTuple};
+use std::sync::Arc;
+
+/// A native function callable from Python, i.e. a `wasmer::NativeFunc`
+/// of any supported signature.
+trait NativeCall {
+    fn call_from_python(
+        &self,
+        py: Python,
+        arguments: &PyTuple,
+        exports: Option<&ExportsInner>,
+    ) -> PyResult<PyObject>;
+}
+
+macro_rules! impl_native_call {
+    ( $( $x:ident ),* ) => {
+        #[allow(non_snake_case, unused_mut, unused_variables)]
+        impl< $( $x, )* Rets > NativeCall for wasmer::NativeFunc<( $( $x, )* ), Rets>
+        where
+            Self: Sync,
+            $( $x: wasmer::FromToNativeWasmType + for<'a> FromPyObject<'a> + Send, )*
+            Rets: wasmer::WasmTypeList + IntoPy<PyObject> + Send,
+        {
+            fn call_from_python(
+                &self,
+                py: Python,
+                arguments: &PyTuple,
+                exports: Option<&ExportsInner>,
+            ) -> PyResult<PyObject> {
+                let arity = <[&str]>::len(&[ $( stringify!($x) ),* ]);
+
+                if arguments.len() != arity {
+                    return Err(to_py_err::<TypeError, _>(format!(
+                        "Function takes {} argument(s) but {} were given",
+                        arity,
+                        arguments.len()
+                    )));
+                }
+
+                let mut arguments = arguments.iter();
+
+                $(
+                    let $x: $x = arguments
+                        .next()
+                        .expect("The arity has been checked")
+                        .extract()?;
+                )*
+
+                let results = call_function(py, exports, None, || self.call( $( $x ),* ))?;
+
+                Ok(results.into_py(py))
+            }
+        }
+    };
+}
+
+impl_native_call!();
+impl_native_call!(A1);
+impl_native_call!(A1, A2);
+impl_native_call!(A1, A2, A3);
+
+/// Builds a `NativeCall` for the given function, by dispatching the
+/// runtime parameter and result types to static types. The depth of
+/// the parameters dispatching is bounded by the number of `_` tokens.
+///
+/// It returns `None` if the signature is not supported.
+macro_rules! native_call {
+    // Dispatch on the results, once all the parameters are known.
+    (@results $function:ident, ( $( $params:ty ),* ), $results:expr) => {
+        match $results {
+            [] => native_call!(@build $function, ( $( $params ),* ), ()),
+            [wasmer::Type::I32] => native_call!(@build $function, ( $( $params ),* ), i32),
+            [wasmer::Type::I64] => native_call!(@build $function, ( $( $params ),* ), i64),
+            [wasmer::Type::F32] => native_call!(@build $function, ( $( $params ),* ), f32),
+            [wasmer::Type::F64] => native_call!(@build $function, ( $( $params ),* ), f64),
+            _ => None,
+        }
+    };
+
+    (@build $function:ident, ( $( $params:ty ),* ), $results:ty) => {
+        Some(
+            $function
+                .native::<( $( $params, )* ), $results>()
+                .map(|native| Box::new(native) as Box<dyn NativeCall + Send>),
+        )
+    };
+
+    // Maximum depth reached: there must be no more parameters.
+    (@params $function:ident, [], ( $( $params:ty ),* ), $rest:expr, $results:expr) => {
+        match $rest {
+            [] => native_call!(@results $function, ( $( $params ),* ), $results),
+            _ => None,
+        }
+    };
+
+    // Dispatch on the next parameter.
+    (@params $function:ident, [ _ $( $depth:tt )* ], ( $( $params:ty ),* ), $rest:expr, $results:expr) => {
+        match $rest {
+            [] => native_call!(@results $function, ( $( $params ),* ), $results),
+            [wasmer::Type::I32, rest @ ..] => native_call!(@params $function, [ $( $depth )* ], ( $( $params, )* i32 ), rest, $results),
+            [wasmer::Type::I64, rest @ ..] => native_call!(@params $function, [ $( $depth )* ], ( $( $params, )* i64 ), rest, $results),
+            [wasmer::Type::F32, rest @ ..] => native_call!(@params $function, [ $( $depth )* ], ( $( $params, )* f32 ), rest, $results),
+            [wasmer::Type::F64, rest @ ..] => native_call!(@params $function, [ $( $depth )* ], ( $( $params, )* f64 ), rest, $results),
+            _ => None,
+        }
+    };
+
+    ($function:ident, $params:expr, $results:expr) => {
+        native_call!(@params $function, [ _ _ _ ], (), $params, $results)
+    };
+}
+
+/// A native function is a specialised version of a `Function`, for a
+/// static signature. It skips the dynamic conversion of the
+/// arguments and the results, which makes it faster to call
+/// repeatedly, e.g. in a hot loop.
+///
+/// It is built by `Function.native`. Supported signatures have up to
+/// 3 parameters and 0 or 1 result, of types `i32`, `i64`, `f32` or
+/// `f64`.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, NativeFunction
+///
+/// module = Module(
+///     Store(),
+///     """
+///     (module
+///       (type (func (param i32 i32) (result i32)))
+///       (func (type 0)
+///         local.get 0
+///         local.get 1
+///         i32.add)
+///       (export "sum" (func 0)))
+///     """
+/// )
+/// instance = Instance(module)
+/// sum = instance.exports.sum.native()
+///
+/// assert isinstance(sum, NativeFunction)
+/// assert sum(1, 2) == 3
+/// ```
+#[pyclass]
+pub struct NativeFunction {
+    inner: Box<dyn NativeCall + Send>,
+    exports: Option<Arc<ExportsInner>>,
+}
+
+impl NativeFunction {
+    pub(crate) fn raw_new(
+        function: &wasmer::Function,
+        params: &[wasmer::Type],
+        results: &[wasmer::Type],
+        exports: Option<Arc<ExportsInner>>,
+    ) -> PyResult<Self> {
+        let inner = native_call!(function, params, results)
+            .ok_or_else(|| {
+                to_py_err::<TypeError, _>(format!(
+                    "The signature `{:?} -> {:?}` is not supported by native functions",
+                    params, results
+                ))
+            })?
+            .map_err(to_py_err::<TypeError, _>)?;
+
+        Ok(Self { inner, exports })
+    }
+}
+
+#[pymethods]
+impl NativeFunction {
+    /// Calls the native function as a regular Python function.
+    #[call]
+    #[args(arguments = "*")]
+    fn __call__(&self, py: Python, arguments: &PyTuple) -> PyResult<PyObject> {
+        self.inner
+            .call_from_python(py, arguments, self.exports.as_deref())
+    }
+}
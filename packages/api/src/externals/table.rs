@@ -1,7 +1,18 @@
 use crate::{
-    errors::to_py_err, store::Store, types::TableType, values::Value, wasmer_inner::wasmer,
+    errors::to_py_err,
+    exports::ExportsInner,
+    store::Store,
+    types::TableType,
+    values::{to_py_object_from, to_wasm_value},
+    wasmer_inner::wasmer,
 };
-use pyo3::{exceptions::RuntimeError, prelude::*};
+use pyo3::{
+    class::sequence::PySequenceProtocol,
+    exceptions::{IndexError, RuntimeError},
+    prelude::*,
+    pycell::PyRef,
+};
+use std::{convert::TryFrom, sync::Arc};
 
 /// A WebAssembly table instance.
 ///
@@ -11,35 +22,124 @@ use pyo3::{exceptions::RuntimeError, prelude::*};
 /// A table created by the host or in WebAssembly code will be
 /// accessible and mutable from both host and WebAssembly.
 ///
+/// The elements of a `funcref` table are `Function` objects, the
+/// elements of an `externref` table are any Python objects, and
+/// `None` is the null reference for both. They can be read and written with
+/// `Table.get` and `Table.set`, or with the sequence protocol, i.e.
+/// `table[index]`, `len(table)`, or by iterating over the table.
+///
+/// The `initial_value` is the value of the elements of a new table;
+/// it defaults to the null reference.
+///
 /// Specification: https://webassembly.github.io/spec/core/exec/runtime.html#table-instances
-#[pyclass(unsendable)]
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, Table, TableType, Type
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (table (export "table") 1 funcref)
+///       (func $answer (export "answer") (result i32) i32.const 42)
+///       (elem (i32.const 0) $answer))
+///     """
+/// )
+/// instance = Instance(module)
+/// table = instance.exports.table
+///
+/// assert len(table) == 1
+/// assert table[0]() == 42
+///
+/// table.grow(1)
+/// table[1] = instance.exports.answer
+///
+/// assert table.get(1)() == 42
+///
+/// # A table created by the host.
+/// table = Table(store, TableType(Type.FUNC_REF, minimum=2))
+///
+/// assert list(table) == [None, None]
+/// ```
+#[pyclass]
 #[text_signature = "(store, table_type, initial_value)"]
 pub struct Table {
     inner: wasmer::Table,
+
+    /// The exports of the instance the table belongs to, if any, see
+    /// `Table::with_exports`. The functions read from the table are
+    /// attached to them too.
+    exports: Option<Arc<ExportsInner>>,
 }
 
 impl Table {
     pub fn raw_new(inner: wasmer::Table) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            exports: None,
+        }
+    }
+
+    /// Attaches the exports of the instance the table belongs to,
+    /// so that the instance stays counted by the instance limit of
+    /// its store as long as the table is alive, see `Limits`.
+    pub(crate) fn with_exports(self, exports: Arc<ExportsInner>) -> Self {
+        Self {
+            exports: Some(exports),
+            ..self
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Table {
         &self.inner
     }
+
+    /// Converts a Python value to an element of a table of type
+    /// `ty`. `None` is the null reference.
+    fn to_element(ty: wasmer::Type, value: Option<&PyAny>) -> PyResult<wasmer::Value> {
+        match value {
+            Some(value) => to_wasm_value((value, ty)),
+            None => Ok(match ty {
+                wasmer::Type::FuncRef => wasmer::Value::FuncRef(None),
+                _ => wasmer::Value::null(),
+            }),
+        }
+    }
+
+    fn check_index(&self, index: u32) -> PyResult<()> {
+        let size = self.inner.size();
+
+        if index >= size {
+            return Err(to_py_err::<IndexError, _>(format!(
+                "Out of bound: Index {} is larger than the table size {}",
+                index, size
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn element(&self, index: u32) -> PyResult<wasmer::Value> {
+        self.check_index(index)?;
+
+        Ok(self.inner.get(index).expect("The index has been checked"))
+    }
 }
 
 #[pymethods]
 impl Table {
     #[new]
-    fn new(store: &Store, table_type: &TableType, initial_value: &Value) -> PyResult<Self> {
-        Ok(Self {
-            inner: wasmer::Table::new(
-                store.inner(),
-                table_type.into(),
-                initial_value.inner().clone(),
-            )
-            .map_err(to_py_err::<RuntimeError, _>)?,
-        })
+    fn new(store: &Store, table_type: &TableType, initial_value: Option<&PyAny>) -> PyResult<Self> {
+        let table_type: wasmer::TableType = table_type.into();
+        let initial_value = Self::to_element(table_type.ty, initial_value)?;
+
+        Ok(Self::raw_new(
+            wasmer::Table::new(store.inner(), table_type, initial_value)
+                .map_err(to_py_err::<RuntimeError, _>)?,
+        ))
     }
 
     /// Gets the table size (in elements).
@@ -80,4 +180,148 @@ impl Table {
     fn ty(&self) -> TableType {
         self.inner.ty().into()
     }
+
+    /// Gets the element at `index`. For a `funcref` table, it is a
+    /// `Function` object; for an `externref` table, it is the Python
+    /// object. It is `None` for a null reference.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Table, TableType, Type
+    ///
+    /// table = Table(Store(), TableType(Type.FUNC_REF, minimum=1))
+    ///
+    /// assert table.get(0) == None
+    /// ```
+    #[text_signature = "($self, index)"]
+    fn get(&self, py: Python, index: u32) -> PyResult<PyObject> {
+        let element = self.element(index)?;
+
+        to_py_object_from(py, self.exports.clone())(&element)
+    }
+
+    /// Sets the element at `index`. For a `funcref` table, the
+    /// value is a `Function` object; for an `externref` table, it is
+    /// any Python object. It is `None` for a null reference.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Table, TableType, Type
+    ///
+    /// store = Store()
+    /// instance = Instance(Module(store, '(module (func (export "f")))'))
+    /// table = Table(store, TableType(Type.FUNC_REF, minimum=1))
+    ///
+    /// table.set(0, instance.exports.f)
+    ///
+    /// assert table.get(0) != None
+    /// ```
+    #[text_signature = "($self, index, value)"]
+    fn set(&self, index: u32, value: &PyAny) -> PyResult<()> {
+        self.check_index(index)?;
+
+        let element = Self::to_element(self.inner.ty().ty, Some(value))?;
+
+        self.inner
+            .set(index, element)
+            .map_err(to_py_err::<RuntimeError, _>)
+    }
+
+    /// Grows the table by `delta` elements, initialized with
+    /// `initial_value` (the null reference by default). It returns
+    /// the previous size of the table.
+    ///
+    /// It raises a `RuntimeError` if the table cannot grow, e.g. if
+    /// its maximum size would be exceeded.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Table, TableType, Type
+    ///
+    /// table = Table(Store(), TableType(Type.FUNC_REF, minimum=1, maximum=3))
+    ///
+    /// assert table.grow(2) == 1
+    /// assert table.size == 3
+    /// ```
+    #[text_signature = "($self, delta, initial_value)"]
+    fn grow(&self, delta: u32, initial_value: Option<&PyAny>) -> PyResult<u32> {
+        let initial_value = Self::to_element(self.inner.ty().ty, initial_value)?;
+
+        self.inner
+            .grow(delta, initial_value)
+            .map_err(to_py_err::<RuntimeError, _>)
+    }
+
+    /// Copies `length` elements from the `source` table (this table
+    /// by default), starting at `source_index`, into this table,
+    /// starting at `destination_index`. The ranges can overlap.
+    ///
+    /// It raises a `RuntimeError` if one of the ranges is out of
+    /// bounds.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Table, TableType, Type
+    ///
+    /// store = Store()
+    /// instance = Instance(Module(store, '(module (func (export "f")))'))
+    /// source = Table(store, TableType(Type.FUNC_REF, minimum=2), instance.exports.f)
+    /// table = Table(store, TableType(Type.FUNC_REF, minimum=4))
+    ///
+    /// table.copy(1, 0, 2, source=source)
+    ///
+    /// assert [element is None for element in table] == [True, False, False, True]
+    /// ```
+    #[text_signature = "($self, destination_index, source_index, length, source)"]
+    fn copy(
+        &self,
+        destination_index: u32,
+        source_index: u32,
+        length: u32,
+        source: Option<PyRef<Table>>,
+    ) -> PyResult<()> {
+        let source = source.as_ref().map_or(&self.inner, |source| &source.inner);
+
+        wasmer::Table::copy(&self.inner, destination_index, source, source_index, length)
+            .map_err(to_py_err::<RuntimeError, _>)
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for Table {
+    fn __len__(&self) -> usize {
+        self.inner.size() as usize
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<PyObject> {
+        let element = self.element(to_index(index)?)?;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        to_py_object_from(py, self.exports.clone())(&element)
+    }
+
+    fn __setitem__(&mut self, index: isize, value: &PyAny) -> PyResult<()> {
+        self.set(to_index(index)?, value)
+    }
+}
+
+fn to_index(index: isize) -> PyResult<u32> {
+    if index < 0 {
+        return Err(to_py_err::<IndexError, _>(
+            "Out of bound: Index cannot be negative",
+        ));
+    }
+
+    u32::try_from(index).map_err(|_| {
+        to_py_err::<IndexError, _>(format!(
+            "Out of bound: Index {} is larger than the maximum table size",
+            index
+        ))
+    })
 }
@@ -129,7 +129,7 @@ use pyo3::{
 /// ```
 ///
 /// etc.
-#[pyclass(unsendable)]
+#[pyclass]
 #[text_signature = "()"]
 pub struct ImportObject {
     inner: wasmer::ImportObject,
@@ -1,6 +1,11 @@
 use crate::{
-    errors::to_py_err, exports::Exports, import_object::ImportObject, module::Module,
-    wasmer_inner::wasmer,
+    errors::{self, instantiation_error_to_py_err, to_py_err},
+    exports::Exports,
+    import_object::ImportObject,
+    interruption::{call_function, InterruptHandle},
+    metering::Metering,
+    module::Module,
+    wasmer_inner::{wasmer, wasmer_engines::START_FUNCTION},
 };
 use pyo3::{exceptions::RuntimeError, prelude::*};
 
@@ -82,11 +87,12 @@ use pyo3::{exceptions::RuntimeError, prelude::*};
 /// # Let's test it!
 /// assert instance.exports.add_one(41) == 42
 /// ```
-#[pyclass(unsendable)]
+#[pyclass]
 #[text_signature = "(module, import_object)"]
 pub struct Instance {
     #[allow(unused)]
     inner: wasmer::Instance,
+    metering: Option<Metering>,
 
     /// The exports of the instance, as an object of kind `Exports`.
     ///
@@ -108,22 +114,64 @@ impl Instance {
         module: &Module,
         import_object: Option<&ImportObject>,
     ) -> Result<Self, InstanceError> {
+        let parameter_names = module.parameter_names().clone();
+        let metered = module.is_metered();
+        let slot = match module.instance_limit() {
+            Some(instance_limit) => Some(instance_limit.acquire().ok_or_else(|| {
+                InstanceError::PyErr(to_py_err::<errors::InstantiationError, _>(format!(
+                    "The number of instances exceeds the limit of {}",
+                    instance_limit.maximum()
+                )))
+            })?),
+            None => None,
+        };
         let module = module.inner();
+        let imports = match import_object {
+            Some(import_object) => import_object.inner().clone(),
+            None => wasmer::imports! {},
+        };
 
-        let instance = match import_object {
-            Some(import_object) => wasmer::Instance::new(&module, import_object.inner()),
-            None => wasmer::Instance::new(&module, &wasmer::imports! {}),
+        let instance =
+            wasmer::Instance::new(module, &imports).map_err(InstanceError::InstantiationError)?;
+
+        let metering = if metered {
+            Some(Metering::new(&instance.exports).ok_or_else(|| {
+                InstanceError::PyErr(to_py_err::<errors::InstantiationError, _>(
+                    "The store has metering, but the module has not been compiled with metering",
+                ))
+            })?)
+        } else {
+            None
         };
-        let instance = instance.map_err(InstanceError::InstantiationError)?;
 
-        let exports =
-            Py::new(py, Exports::new(instance.exports.clone())).map_err(InstanceError::PyErr)?;
+        let exports = Exports::new(&instance.exports, parameter_names, metering.clone(), slot);
+
+        // The start function is deferred by the compiler, so that it
+        // runs once the environments of the host functions are
+        // initialized, see `CallerContext`.
+        if let Ok(start) = instance.exports.get_function(START_FUNCTION) {
+            call_function(py, Some(exports.shared().as_ref()), None, || {
+                start.call(&[])
+            })
+            .map_err(InstanceError::PyErr)?;
+        }
+
+        let exports = Py::new(py, exports).map_err(InstanceError::PyErr)?;
 
         Ok(Instance {
             inner: instance,
+            metering,
             exports,
         })
     }
+
+    fn metering(&self) -> PyResult<&Metering> {
+        self.metering.as_ref().ok_or_else(|| {
+            to_py_err::<RuntimeError, _>(
+                "The module has not been compiled with metering; see `engine.Metering`",
+            )
+        })
+    }
 }
 
 #[pymethods]
@@ -131,8 +179,73 @@ impl Instance {
     #[new]
     fn new(py: Python, module: &Module, import_object: Option<&ImportObject>) -> PyResult<Self> {
         Instance::raw_new(py, &module, import_object).map_err(|error| match error {
-            InstanceError::InstantiationError(error) => to_py_err::<RuntimeError, _>(error),
+            InstanceError::InstantiationError(error) => instantiation_error_to_py_err(py, error),
             InstanceError::PyErr(error) => error,
         })
     }
+
+    /// The remaining metering points of the instance, when the module
+    /// has been compiled by an engine with metering (see
+    /// `engine.Metering`). It is 0 once the points are exhausted.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import engine, Store, Module, Instance
+    /// from wasmer_compiler_cranelift import Compiler
+    ///
+    /// store = Store(engine.JIT(Compiler, metering=engine.Metering(initial_points=100)))
+    /// module = Module(store, open('tests/tests.wasm', 'rb').read())
+    /// instance = Instance(module)
+    ///
+    /// assert instance.remaining_points == 100
+    /// ```
+    #[getter]
+    fn remaining_points(&self) -> PyResult<u64> {
+        Ok(self.metering()?.remaining_points())
+    }
+
+    /// A handle to interrupt the WebAssembly code running in the
+    /// instance, from any thread. The module must have been compiled
+    /// by an engine with a compiler.
+    ///
+    /// ## Example
+    ///
+    /// See the `InterruptHandle` class.
+    #[getter]
+    fn interrupt_handle(&self, py: Python) -> PyResult<InterruptHandle> {
+        let exports = self.exports.as_ref(py).borrow().clone();
+
+        if exports.interruption().is_none() {
+            return Err(to_py_err::<RuntimeError, _>(
+                "The module has not been compiled with interruption checks; it must be compiled by an engine with a compiler",
+            ));
+        }
+
+        Ok(InterruptHandle::new(exports))
+    }
+
+    /// Sets the remaining metering points of the instance, e.g. to
+    /// resume the execution once the points are exhausted.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import engine, Store, Module, Instance
+    /// from wasmer_compiler_cranelift import Compiler
+    ///
+    /// store = Store(engine.JIT(Compiler, metering=engine.Metering(initial_points=100)))
+    /// module = Module(store, open('tests/tests.wasm', 'rb').read())
+    /// instance = Instance(module)
+    ///
+    /// instance.set_remaining_points(42)
+    ///
+    /// assert instance.remaining_points == 42
+    /// ```
+    #[text_signature = "($self, points)"]
+    fn set_remaining_points(&self, points: u64) -> PyResult<()> {
+        self.metering()?.set_remaining_points(points);
+
+        Ok(())
+    }
 }
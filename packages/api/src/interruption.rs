@@ -0,0 +1,438 @@
+use crate::{
+    errors::{instance_runtime_error_to_py_err, to_py_err, to_py_err_with_attributes, Interrupted},
+    exports::{Exports, ExportsInner},
+    wasmer_inner::{wasmer, wasmer_engines::INTERRUPTED_GLOBAL},
+};
+use once_cell::sync::OnceCell;
+use pyo3::{exceptions::RuntimeError, prelude::*};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The interruption flag of an instance whose module has been
+/// compiled with interruption checks, i.e. by an engine with a
+/// compiler. The guest stops at the next check (a safepoint) once the
+/// flag is set. The flag is separate from the metering points, so
+/// that interrupting the guest does not consume them.
+///
+/// The flag is shared by all the calls running in the instance, so a
+/// call with a timeout runs alone in the instance, see `CallGate`:
+/// its timeout never interrupts the other calls.
+///
+/// The flag is a global exported by the instance, which is held
+/// rather than the instance itself, so that an environment of a host
+/// function can hold it too without keeping its own instance alive.
+#[derive(Clone)]
+pub(crate) struct Interruption {
+    interrupted: wasmer::Global,
+    gate: Arc<CallGate>,
+}
+
+/// The error of a call made by `Interruption::call`.
+pub(crate) enum CallError {
+    /// The call has failed, possibly because it has been interrupted,
+    /// either by its timeout or by an `InterruptHandle`.
+    Runtime {
+        error: wasmer::RuntimeError,
+        interrupted: bool,
+    },
+
+    /// The timeout has expired before the other calls running in the
+    /// instance have returned, so the call has not started.
+    Busy,
+}
+
+impl Interruption {
+    /// Gives access to the interruption flag of an instance, from its
+    /// exports. It returns `None` if the module of the instance has
+    /// not been compiled with interruption checks.
+    pub(crate) fn new(exports: &wasmer::Exports) -> Option<Self> {
+        Some(Self {
+            interrupted: exports.get_global(INTERRUPTED_GLOBAL).ok()?.clone(),
+            gate: Arc::default(),
+        })
+    }
+
+    /// Interrupts the guest, by setting its interruption flag. It can
+    /// be called from any thread.
+    pub(crate) fn interrupt(&self) {
+        self.interrupted
+            .set(wasmer::Value::I32(1))
+            .expect("The interruption flag is a mutable `i32` global");
+    }
+
+    /// Cancels the pending interruption, if any. It returns whether
+    /// an interruption was pending.
+    fn cancel(&self) -> bool {
+        if !matches!(self.interrupted.get(), wasmer::Value::I32(1)) {
+            return false;
+        }
+
+        self.interrupted
+            .set(wasmer::Value::I32(0))
+            .expect("The interruption flag is a mutable `i32` global");
+
+        true
+    }
+
+    /// Runs `call` in the instance, and interrupts it if it has not
+    /// returned before `timeout`.
+    pub(crate) fn call<F, R>(&self, timeout: Option<Duration>, call: F) -> Result<R, CallError>
+    where
+        F: FnOnce() -> Result<R, wasmer::RuntimeError>,
+    {
+        // A timeout too big to be represented never expires.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+
+        let _entry = match deadline {
+            Some(deadline) => self.gate.enter_alone(deadline).ok_or(CallError::Busy)?,
+            None => self.gate.enter(),
+        };
+
+        let watchdog = Watchdog::get();
+        let call_id = deadline.map(|deadline| watchdog.watch(deadline, self.clone()));
+
+        let result = call();
+
+        // The timeout has possibly expired right after `call` has
+        // returned, in which case the interruption must not stay
+        // pending. The flag is reset before leaving the gate, so that
+        // it never reaches another call.
+        let timed_out = call_id.map_or(false, |call_id| watchdog.unwatch(call_id));
+
+        match result {
+            Ok(result) => {
+                if timed_out {
+                    self.cancel();
+                }
+
+                Ok(result)
+            }
+
+            Err(error) => Err(CallError::Runtime {
+                error,
+                interrupted: self.cancel() || timed_out,
+            }),
+        }
+    }
+}
+
+/// Calls a function of an instance with the GIL released, and
+/// converts the error, if any, see `Interruption::call`. `exports`
+/// are the exports of the instance the function comes from, if
+/// known.
+pub(crate) fn call_function<F, R>(
+    py: Python,
+    exports: Option<&ExportsInner>,
+    timeout: Option<Duration>,
+    call: F,
+) -> PyResult<R>
+where
+    F: FnOnce() -> Result<R, wasmer::RuntimeError> + Send,
+    R: Send,
+{
+    let interruption = exports.and_then(ExportsInner::interruption);
+    let metering = exports.and_then(ExportsInner::metering);
+
+    if timeout.is_some() && interruption.is_none() {
+        return Err(to_py_err::<RuntimeError, _>(
+            "The function cannot be interrupted; it must be exported by an instance compiled with a compiler",
+        ));
+    }
+
+    // Release the GIL while the WebAssembly code runs, so that other
+    // Python threads can run too. Host functions re-acquire it when
+    // they are called.
+    let result = py.allow_threads(|| match interruption {
+        Some(interruption) => interruption.call(timeout, call),
+        None => call().map_err(|error| CallError::Runtime {
+            error,
+            interrupted: false,
+        }),
+    });
+
+    result.map_err(|error| match error {
+        CallError::Runtime { error, interrupted } => {
+            instance_runtime_error_to_py_err(py, error, interrupted, metering)
+        }
+
+        CallError::Busy => {
+            let message = "The timeout has expired while other calls were running in the instance";
+
+            to_py_err_with_attributes::<Interrupted, _>(
+                py,
+                message,
+                &[
+                    ("message", message.to_object(py)),
+                    ("code", py.None()),
+                    ("frames", Vec::<PyObject>::new().to_object(py)),
+                ],
+            )
+        }
+    })
+}
+
+thread_local! {
+    /// The gates of the calls running in the current thread. A
+    /// function called back by a host function runs within the call
+    /// of its caller, so it does not enter the gate again.
+    static ENTERED_GATES: RefCell<Vec<*const CallGate>> = RefCell::new(Vec::new());
+}
+
+/// Gives access to an instance to the calls running in it: either
+/// several calls without timeout, or a single call with a timeout.
+#[derive(Default)]
+struct CallGate {
+    calls: Mutex<GateCalls>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct GateCalls {
+    /// The number of calls without timeout running in the instance.
+    shared: usize,
+
+    /// Whether a call with a timeout runs in the instance.
+    alone: bool,
+}
+
+impl CallGate {
+    /// Whether the current thread runs a call in the instance, i.e.
+    /// whether the call to enter is called back by a host function.
+    fn is_entered(&self) -> bool {
+        ENTERED_GATES.with(|gates| gates.borrow().contains(&(self as *const _)))
+    }
+
+    /// Enters the gate for a call without timeout, waiting for the
+    /// call with a timeout, if any, to return.
+    fn enter(&self) -> GateEntry {
+        if self.is_entered() {
+            return GateEntry::new(self, None);
+        }
+
+        let mut calls = self.calls.lock().unwrap();
+
+        while calls.alone {
+            calls = self.condvar.wait(calls).unwrap();
+        }
+
+        calls.shared += 1;
+
+        GateEntry::new(self, Some(false))
+    }
+
+    /// Enters the gate for a call with a timeout, waiting for all the
+    /// other calls to return, but not after `deadline`.
+    fn enter_alone(&self, deadline: Instant) -> Option<GateEntry> {
+        if self.is_entered() {
+            return Some(GateEntry::new(self, None));
+        }
+
+        let mut calls = self.calls.lock().unwrap();
+
+        while calls.alone || calls.shared > 0 {
+            let now = Instant::now();
+
+            if now >= deadline {
+                return None;
+            }
+
+            calls = self.condvar.wait_timeout(calls, deadline - now).unwrap().0;
+        }
+
+        calls.alone = true;
+
+        Some(GateEntry::new(self, Some(true)))
+    }
+}
+
+/// A call entered in a `CallGate`, which leaves it when dropped.
+struct GateEntry<'a> {
+    gate: &'a CallGate,
+
+    /// Whether the call runs alone, or `None` if it is called back by
+    /// a call already running in the instance, in which case it has
+    /// not entered the gate itself.
+    alone: Option<bool>,
+}
+
+impl<'a> GateEntry<'a> {
+    fn new(gate: &'a CallGate, alone: Option<bool>) -> Self {
+        ENTERED_GATES.with(|gates| gates.borrow_mut().push(gate));
+
+        Self { gate, alone }
+    }
+}
+
+impl Drop for GateEntry<'_> {
+    fn drop(&mut self) {
+        ENTERED_GATES.with(|gates| gates.borrow_mut().pop());
+
+        if let Some(alone) = self.alone {
+            let mut calls = self.gate.calls.lock().unwrap();
+
+            if alone {
+                calls.alone = false;
+            } else {
+                calls.shared -= 1;
+            }
+
+            self.gate.condvar.notify_all();
+        }
+    }
+}
+
+/// A single thread interrupting the calls whose timeout has expired,
+/// for all the instances, see `Interruption::call`.
+#[derive(Default)]
+struct Watchdog {
+    calls: Mutex<WatchedCalls>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct WatchedCalls {
+    next_call_id: u64,
+    deadlines: BTreeMap<u64, (Instant, Interruption)>,
+}
+
+impl Watchdog {
+    /// Returns the watchdog, and starts its thread the first time.
+    fn get() -> &'static Self {
+        static WATCHDOG: OnceCell<Watchdog> = OnceCell::new();
+
+        WATCHDOG.get_or_init(|| {
+            thread::Builder::new()
+                .name("wasmer-watchdog".to_string())
+                .spawn(|| Self::get().run())
+                .expect("Failed to start the watchdog thread");
+
+            Self::default()
+        })
+    }
+
+    fn run(&self) {
+        let mut calls = self.calls.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+            let expired_call_ids = calls
+                .deadlines
+                .iter()
+                .filter(|(_, (deadline, _))| *deadline <= now)
+                .map(|(call_id, _)| *call_id)
+                .collect::<Vec<_>>();
+
+            for call_id in expired_call_ids {
+                if let Some((_, interruption)) = calls.deadlines.remove(&call_id) {
+                    interruption.interrupt();
+                }
+            }
+
+            let next_deadline = calls
+                .deadlines
+                .values()
+                .map(|(deadline, _)| *deadline)
+                .min();
+
+            calls = match next_deadline {
+                Some(deadline) => {
+                    self.condvar
+                        .wait_timeout(calls, deadline.saturating_duration_since(now))
+                        .unwrap()
+                        .0
+                }
+
+                None => self.condvar.wait(calls).unwrap(),
+            };
+        }
+    }
+
+    /// Watches a call, until `unwatch` is called with the returned
+    /// identifier.
+    fn watch(&self, deadline: Instant, interruption: Interruption) -> u64 {
+        let mut calls = self.calls.lock().unwrap();
+        let call_id = calls.next_call_id;
+
+        calls.next_call_id += 1;
+        calls.deadlines.insert(call_id, (deadline, interruption));
+        self.condvar.notify_one();
+
+        call_id
+    }
+
+    /// Stops watching a call. It returns whether the call has been
+    /// interrupted.
+    fn unwatch(&self, call_id: u64) -> bool {
+        self.calls
+            .lock()
+            .unwrap()
+            .deadlines
+            .remove(&call_id)
+            .is_none()
+    }
+}
+
+/// A handle to interrupt the WebAssembly code running in an
+/// `Instance`, built by `Instance.interrupt_handle`.
+///
+/// The handle can be triggered from any thread. The guest stops at
+/// the next safepoint, and the call raises a
+/// `wasmer.errors.Interrupted` exception. Safepoints are compiled in
+/// every module by the engines with a compiler, with or without
+/// metering.
+///
+/// Interrupting the instance interrupts all the calls running in it.
+/// It does not consume its metering points, if any, and the instance
+/// can be used again once the exception is raised. An interruption
+/// triggered while no WebAssembly code is running is pending until
+/// the next call.
+///
+/// To interrupt a single call after some time, see `Function.call`.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import errors, Store, Module, Instance
+/// import threading
+///
+/// module = Module(Store(), '(module (func (export "loop") (loop br 0)))')
+/// instance = Instance(module)
+///
+/// handle = instance.interrupt_handle
+/// threading.Timer(0.1, handle.interrupt).start()
+///
+/// try:
+///     instance.exports.loop()
+/// except errors.Interrupted:
+///     pass
+/// ```
+#[pyclass]
+pub struct InterruptHandle {
+    /// The exports of an interruptible instance. The handle holds
+    /// them, rather than the interruption flag only, so that the
+    /// instance is counted by the instance limit of its store as long
+    /// as the handle is alive, see `Limits`.
+    exports: Exports,
+}
+
+impl InterruptHandle {
+    pub(crate) fn new(exports: Exports) -> Self {
+        Self { exports }
+    }
+}
+
+#[pymethods]
+impl InterruptHandle {
+    /// Interrupts the WebAssembly code running in the instance.
+    #[text_signature = "($self)"]
+    fn interrupt(&self, py: Python) {
+        if let Some(interruption) = self.exports.interruption() {
+            py.allow_threads(|| interruption.interrupt());
+        }
+    }
+}
@@ -6,22 +6,32 @@ use pyo3::{
 
 pub(crate) mod wasmer_inner {
     pub use wasmer;
+    pub use wasmer_cache;
     pub use wasmer_engines;
+    pub use wasmer_middlewares;
     pub use wasmer_types;
     pub use wasmer_wasi;
 }
 
+mod cache;
 mod engines;
 mod errors;
 mod exports;
 mod externals;
 mod import_object;
 mod instance;
+mod interruption;
 mod memory;
+mod metering;
 mod module;
+mod names;
+mod serialization;
 mod store;
 mod target;
+mod traps;
+mod tunables;
 mod types;
+mod validation;
 mod values;
 mod wasi;
 mod wat;
@@ -79,6 +89,30 @@ mod wat;
 /// * It is possible to read and write `Memory` data with the Python
 ///   buffer protocol with `Buffer`.
 ///
+/// ## Threads
+///
+/// All the classes can be shared between Python threads, e.g. with a
+/// `concurrent.futures.ThreadPoolExecutor`:
+///
+/// * `Store`, the engines and `Module` are immutable once created. A
+///   module can be compiled once and instantiated from many threads.
+///   The only shared mutable state is the number of living instances
+///   when the store has an `instances` limit (see `Limits`): it is an
+///   atomic counter, updated without any lock, so that concurrent
+///   instantiations never exceed the limit,
+/// * `Instance`, `Exports` and the externals (`Function`, `Memory`,
+///   `Global` and `Table`) can be used from any thread. Calling the
+///   same function from several threads at once is allowed, but the
+///   calls share the state of the instance (memories, globals,
+///   tables): it is up to the program to synchronize them, exactly
+///   like shared memory between native threads,
+/// * Reading or writing a `Memory` through a view or a `Buffer` is
+///   not synchronized with the WebAssembly code running in another
+///   thread.
+///
+/// The GIL is released while WebAssembly code runs, so that calls
+/// happening in different threads actually run in parallel.
+///
 /// Have fun!
 #[pymodule]
 fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
@@ -120,21 +154,29 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
 
     // Classes.
     module.add_class::<exports::Exports>()?;
+    module.add_class::<externals::CallerContext>()?;
     module.add_class::<externals::Function>()?;
     module.add_class::<externals::Global>()?;
     module.add_class::<externals::Memory>()?;
     module.add_class::<externals::Table>()?;
     module.add_class::<import_object::ImportObject>()?;
     module.add_class::<instance::Instance>()?;
+    module.add_class::<interruption::InterruptHandle>()?;
     module.add_class::<memory::Buffer>()?;
+    module.add_class::<memory::Float32Array>()?;
+    module.add_class::<memory::Float64Array>()?;
     module.add_class::<memory::Int16Array>()?;
     module.add_class::<memory::Int32Array>()?;
+    module.add_class::<memory::Int64Array>()?;
     module.add_class::<memory::Int8Array>()?;
     module.add_class::<memory::Uint16Array>()?;
     module.add_class::<memory::Uint32Array>()?;
+    module.add_class::<memory::Uint64Array>()?;
     module.add_class::<memory::Uint8Array>()?;
     module.add_class::<module::Module>()?;
+    module.add_class::<externals::NativeFunction>()?;
     module.add_class::<store::Store>()?;
+    module.add_class::<tunables::Limits>()?;
     module.add_class::<types::ExportType>()?;
     module.add_class::<types::FunctionType>()?;
     module.add_class::<types::GlobalType>()?;
@@ -143,6 +185,25 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<types::TableType>()?;
     module.add_class::<values::Value>()?;
 
+    // Marker types, to annotate host functions.
+    let typing_module = py.import("typing")?;
+    let builtins_module = py.import("builtins")?;
+
+    for (marker, supertype) in &[
+        ("i32", "int"),
+        ("i64", "int"),
+        ("f32", "float"),
+        ("f64", "float"),
+        ("v128", "int"),
+        ("externref", "object"),
+        ("funcref", "object"),
+    ] {
+        module.add(
+            marker,
+            typing_module.call1("NewType", (*marker, builtins_module.get(supertype)?))?,
+        )?;
+    }
+
     // Enums.
     module.add(
         "Type",
@@ -163,13 +224,42 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     )?;
 
     // Modules.
+    module.add_wrapped(wrap_pymodule!(cache))?;
     module.add_wrapped(wrap_pymodule!(engine))?;
+    module.add_wrapped(wrap_pymodule!(errors))?;
     module.add_wrapped(wrap_pymodule!(target))?;
     module.add_wrapped(wrap_pymodule!(wasi))?;
 
     Ok(())
 }
 
+/// Wasmer's caches of compiled modules.
+///
+/// Compiling a module can take time, especially with an optimizing
+/// compiler. A cache stores the compiled artifacts, so that a module
+/// is compiled only once, and then loaded from the cache, even across
+/// processes.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import cache, Store, Module
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     open('tests/tests.wasm', 'rb').read(),
+///     cache=cache.FileSystemCache('/tmp/wasmer-cache')
+/// )
+/// ```
+#[pymodule]
+fn cache(_py: Python, module: &PyModule) -> PyResult<()> {
+    // Classes.
+    module.add_class::<cache::FileSystemCache>()?;
+
+    Ok(())
+}
+
 /// Wasmer Engines.
 ///
 /// Engines are mainly responsible for two things:
@@ -213,16 +303,136 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
 /// engine = engine.JIT(Compiler)
 /// ```
 ///
+/// Create a JIT engine with the Cranelift compiler and metering:
+///
+/// ```py
+/// from wasmer import engine
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// engine = engine.JIT(Compiler, metering=engine.Metering(initial_points=1000))
+/// ```
+///
+/// Create a JIT engine with the Cranelift compiler, and the SIMD
+/// proposal enabled:
+///
+/// ```py
+/// from wasmer import engine
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// engine = engine.JIT(Compiler, features=engine.Features(simd=True))
+/// ```
+///
 /// Engines are stored inside the `wasmer.Store`.
 #[pymodule]
 fn engine(_py: Python, module: &PyModule) -> PyResult<()> {
     // Classes.
+    module.add_class::<engines::Features>()?;
     module.add_class::<engines::JIT>()?;
+    module.add_class::<engines::Metering>()?;
     module.add_class::<engines::Native>()?;
 
     Ok(())
 }
 
+/// Wasmer's errors.
+///
+/// All the exceptions raised by Wasmer live in this module. They are
+/// organized as a hierarchy so that it is possible to catch them
+/// precisely, while still being `RuntimeError` for the ones that
+/// don't care:
+///
+/// * `CompileError`, raised when a module fails to compile,
+///   * `ValidationError`, raised when a module is not a valid
+///     WebAssembly module; it holds the `reason`, the byte `offset`
+///     and the `section` (if any) of the error,
+/// * `InstantiationError`, raised when a module fails to be
+///   instantiated,
+///   * `LinkError`, raised when an import is missing or has the
+///     wrong type; it holds the `module` and `name` of the import (if
+///     any),
+/// * `Trap`, raised when WebAssembly code traps at runtime; it holds
+///   the `message` of the trap, its `code` (a `TrapCode` value, or
+///   `None` if the trap has been raised by the host), and its
+///   `frames`, i.e. the WebAssembly stack trace as a list of
+///   `FrameInfo` objects,
+///   * `MeteringPointsExhausted`, raised when a metered instance has
+///     no more points to execute WebAssembly code, see
+///     `engine.Metering`,
+///   * `Interrupted`, raised when WebAssembly code has been
+///     interrupted, see `InterruptHandle`,
+/// * `MemoryError`, raised when a memory cannot be created or grown;
+///   it holds the `current` number of pages and the
+///   `attempted_delta` when growing failed,
+/// * `DeserializeError`, raised when a serialized module is corrupted,
+///   or has been serialized for another environment (Wasmer version,
+///   engine, target).
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import errors, Store, Module
+///
+/// try:
+///     Module(Store(), open('tests/invalid.wasm', 'rb').read())
+/// except errors.CompileError as error:
+///     assert isinstance(error, RuntimeError)
+/// ```
+#[pymodule]
+fn errors(py: Python, module: &PyModule) -> PyResult<()> {
+    let enum_module = py.import("enum")?;
+
+    // Exceptions.
+    let exceptions = [
+        ("CompileError", py.get_type::<errors::CompileError>()),
+        ("ValidationError", py.get_type::<errors::ValidationError>()),
+        (
+            "InstantiationError",
+            py.get_type::<errors::InstantiationError>(),
+        ),
+        ("LinkError", py.get_type::<errors::LinkError>()),
+        ("Trap", py.get_type::<errors::Trap>()),
+        ("MemoryError", py.get_type::<errors::MemoryError>()),
+        (
+            "DeserializeError",
+            py.get_type::<errors::DeserializeError>(),
+        ),
+        (
+            "MeteringPointsExhausted",
+            py.get_type::<errors::MeteringPointsExhausted>(),
+        ),
+        ("Interrupted", py.get_type::<errors::Interrupted>()),
+    ];
+
+    for (name, exception) in exceptions.iter() {
+        exception.setattr("__module__", "wasmer.errors")?;
+        module.add(name, *exception)?;
+    }
+
+    // Classes.
+    module.add_class::<traps::FrameInfo>()?;
+
+    // Enums.
+    module.add(
+        "TrapCode",
+        enum_module.call1(
+            "IntEnum",
+            PyTuple::new(
+                py,
+                &[
+                    "TrapCode",
+                    traps::TrapCode::iter()
+                        .map(Into::into)
+                        .collect::<Vec<&'static str>>()
+                        .join(" ")
+                        .as_str(),
+                ],
+            ),
+        )?,
+    )?;
+
+    Ok(())
+}
+
 /// Wasmer's compilation targets.
 ///
 /// Wasmer has several compilers used by the engines (`wasmer.engine`)
@@ -1,4 +1,4 @@
-use crate::{errors::to_py_err, wasmer_inner::wasmer};
+use crate::{errors::to_py_err, exports::ExportsInner, wasmer_inner::wasmer};
 use pyo3::{
     class::buffer::PyBufferProtocol,
     exceptions::BufferError,
@@ -12,6 +12,7 @@ use std::{
     ops::Deref,
     os::raw::{c_char, c_int},
     ptr,
+    sync::Arc,
 };
 
 /// Represents a read-and-write buffer over data of a memory.
@@ -53,14 +54,19 @@ use std::{
 /// assert byte_array[0:3] == b'\x01\x02\x03'
 /// assert byte_array[3:9].decode() == 'Wasmer'
 /// ```
-#[pyclass(unsendable)]
+#[pyclass]
 pub struct Buffer {
     memory: wasmer::Memory,
+
+    /// The exports of the instance the memory belongs to, if any, see
+    /// `Memory.with_exports`.
+    #[allow(unused)]
+    exports: Option<Arc<ExportsInner>>,
 }
 
 impl Buffer {
-    pub fn new(memory: wasmer::Memory) -> Self {
-        Buffer { memory }
+    pub fn new(memory: wasmer::Memory, exports: Option<Arc<ExportsInner>>) -> Self {
+        Buffer { memory, exports }
     }
 }
 
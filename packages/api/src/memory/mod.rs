@@ -2,4 +2,7 @@ mod buffer;
 mod views;
 
 pub use buffer::Buffer;
-pub use views::{Int16Array, Int32Array, Int8Array, Uint16Array, Uint32Array, Uint8Array};
+pub use views::{
+    Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, Uint16Array,
+    Uint32Array, Uint64Array, Uint8Array,
+};
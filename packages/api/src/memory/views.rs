@@ -1,14 +1,14 @@
-use crate::{errors::to_py_err, wasmer_inner::wasmer};
+use crate::{errors::to_py_err, exports::ExportsInner, wasmer_inner::wasmer};
 use pyo3::{
     class::PyMappingProtocol,
     exceptions::{IndexError, RuntimeError, ValueError},
     prelude::*,
     types::{PyAny, PyInt, PyLong, PySequence, PySlice},
 };
-use std::{cell::Cell, cmp::min, mem::size_of, ops::Range};
+use std::{cell::Cell, cmp::min, mem::size_of, ops::Range, sync::Arc};
 
 macro_rules! memory_view {
-    ($class_name:ident over $wasm_type:ty | $bytes_per_element:expr) => {
+    ($class_name:ident over $wasm_type:ty | $bytes_per_element:expr, $python_type:expr, $extract_value:ident) => {
         /// Represents a read-and-write view over the data of a
         /// memory.
         ///
@@ -47,6 +47,11 @@ macro_rules! memory_view {
         pub struct $class_name {
             pub(crate) memory: wasmer::Memory,
             pub(crate) offset: usize,
+
+            /// The exports of the instance the memory belongs to, if
+            /// any, see `Memory.with_exports`.
+            #[allow(unused)]
+            pub(crate) exports: Option<Arc<ExportsInner>>,
         }
 
         #[pymethods]
@@ -135,7 +140,8 @@ macro_rules! memory_view {
             /// Sets one or more values in the memory view.
             ///
             /// The `index` and `value` can only be of type slice and
-            /// list, or integer and integer.
+            /// list, or integer and integer (or float for the float
+            /// views).
             fn __setitem__(&mut self, index: &PyAny, value: &PyAny) -> PyResult<()> {
                 let offset = self.offset;
                 let view = self.memory.view::<$wasm_type>();
@@ -191,10 +197,7 @@ macro_rules! memory_view {
                         .cast_as::<PyLong>()
                         .map_err(PyErr::from)
                         .and_then(|pylong| pylong.extract::<isize>()),
-                    value
-                        .cast_as::<PyInt>()
-                        .map_err(PyErr::from)
-                        .and_then(|pyint| pyint.extract::<$wasm_type>()),
+                    $extract_value::<$wasm_type>(value),
                 ) {
                     if index < 0 {
                         return Err(to_py_err::<IndexError, _>(
@@ -216,16 +219,34 @@ macro_rules! memory_view {
                         Ok(())
                     }
                 } else {
-                    Err(to_py_err::<RuntimeError, _>("When setting data to the memory view, the index and the value can only have the following types: Either `int` and `int`, or `slice` and `sequence`"))
+                    Err(to_py_err::<RuntimeError, _>(format!("When setting data to the memory view, the index and the value can only have the following types: Either `int` and `{}`, or `slice` and `sequence`", $python_type)))
                 }
             }
         }
     };
 }
 
-memory_view!(Uint8Array over u8|1);
-memory_view!(Int8Array over i8|1);
-memory_view!(Uint16Array over u16|2);
-memory_view!(Int16Array over i16|2);
-memory_view!(Uint32Array over u32|4);
-memory_view!(Int32Array over i32|4);
+/// Extracts the value set in an integer view: only `int` is allowed.
+fn extract_int<'a, T: FromPyObject<'a>>(value: &'a PyAny) -> PyResult<T> {
+    value
+        .cast_as::<PyInt>()
+        .map_err(PyErr::from)
+        .and_then(|pyint| pyint.extract::<T>())
+}
+
+/// Extracts the value set in a float view: `float`, or anything
+/// convertible to it, like `int`.
+fn extract_float<'a, T: FromPyObject<'a>>(value: &'a PyAny) -> PyResult<T> {
+    value.extract::<T>()
+}
+
+memory_view!(Uint8Array over u8|1, "int", extract_int);
+memory_view!(Int8Array over i8|1, "int", extract_int);
+memory_view!(Uint16Array over u16|2, "int", extract_int);
+memory_view!(Int16Array over i16|2, "int", extract_int);
+memory_view!(Uint32Array over u32|4, "int", extract_int);
+memory_view!(Int32Array over i32|4, "int", extract_int);
+memory_view!(Uint64Array over u64|8, "int", extract_int);
+memory_view!(Int64Array over i64|8, "int", extract_int);
+memory_view!(Float32Array over f32|4, "float", extract_float);
+memory_view!(Float64Array over f64|8, "float", extract_float);
@@ -0,0 +1,61 @@
+use crate::wasmer_inner::wasmer;
+
+/// The metering points of an instance whose module has been compiled
+/// by a metered engine, see `engine.Metering`.
+///
+/// The points are globals exported by the instance, which are held
+/// rather than the instance itself, so that an environment of a host
+/// function can hold them too without keeping its own instance alive.
+#[derive(Clone)]
+pub(crate) struct Metering {
+    remaining_points: wasmer::Global,
+    points_exhausted: wasmer::Global,
+}
+
+/// The global holding the remaining points, exported by the
+/// instances of a module compiled with the
+/// `wasmer_middlewares::Metering` middleware.
+const REMAINING_POINTS_GLOBAL: &str = "wasmer_metering_remaining_points";
+
+/// The global set to 1 once the points are exhausted, exported
+/// alongside `REMAINING_POINTS_GLOBAL`.
+const POINTS_EXHAUSTED_GLOBAL: &str = "wasmer_metering_points_exhausted";
+
+impl Metering {
+    /// Gives access to the metering points of an instance, from its
+    /// exports. It returns `None` if the module of the instance has
+    /// not been compiled with metering, since the points would be
+    /// missing.
+    pub(crate) fn new(exports: &wasmer::Exports) -> Option<Self> {
+        let global = |name| exports.get_global(name).ok().cloned();
+
+        Some(Self {
+            remaining_points: global(REMAINING_POINTS_GLOBAL)?,
+            points_exhausted: global(POINTS_EXHAUSTED_GLOBAL)?,
+        })
+    }
+
+    pub(crate) fn remaining_points(&self) -> u64 {
+        if self.is_exhausted() {
+            return 0;
+        }
+
+        match self.remaining_points.get() {
+            wasmer::Value::I64(points) => points as u64,
+            _ => unreachable!("The remaining points are an `i64` global"),
+        }
+    }
+
+    pub(crate) fn set_remaining_points(&self, points: u64) {
+        self.remaining_points
+            .set(wasmer::Value::I64(points as i64))
+            .expect("The remaining points are a mutable `i64` global");
+        self.points_exhausted
+            .set(wasmer::Value::I32(0))
+            .expect("The exhaustion flag is a mutable `i32` global");
+    }
+
+    pub(crate) fn is_exhausted(&self) -> bool {
+        !matches!(self.points_exhausted.get(), wasmer::Value::I32(0))
+    }
+}
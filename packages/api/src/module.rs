@@ -1,10 +1,22 @@
-use crate::{errors::to_py_err, store::Store, types, wasmer_inner::wasmer};
+use crate::{
+    cache::FileSystemCache,
+    engines::Features,
+    errors::{compile_error_to_py_err, to_py_err, DeserializeError},
+    names::{self, ParameterNames},
+    serialization::{self, Header},
+    store::Store,
+    tunables::InstanceLimit,
+    types, validation,
+    wasmer_inner::{wasmer, wasmer_engines::START_FUNCTION},
+};
+use memmap2::Mmap;
 use pyo3::{
+    buffer::PyBuffer,
     exceptions::{RuntimeError, TypeError},
     prelude::*,
     types::{PyAny, PyBytes, PyList, PyString},
 };
-use std::convert::TryInto;
+use std::{convert::TryInto, fs::File, ops::Deref, path::PathBuf, slice, sync::Arc};
 
 /// A WebAssembly module contains stateless WebAssembly code that has
 /// already been compiled and can be instantiated multiple times.
@@ -16,6 +28,23 @@ use std::convert::TryInto;
 /// `b"\0asm"`), this function will try to to convert the bytes
 /// assuming they correspond to the WebAssembly text format.
 ///
+/// The module can be read from:
+///
+/// * `bytes`, or a `str` containing WAT,
+/// * a path, i.e. an `os.PathLike` object such as `pathlib.Path`; the
+///   file is memory-mapped instead of being read,
+/// * any object implementing the buffer protocol, such as
+///   `bytearray` or `memoryview`; the buffer is read without being
+///   copied, so it must be contiguous,
+/// * a readable file-like object, i.e. an object with a `read`
+///   method, e.g. the result of `open(path, 'rb')`.
+///
+/// Consequently, large modules can be compiled without being first
+/// copied in a Python `bytes` object. Note that the compiler needs
+/// the whole module at once: a file-like object is not streamed, it
+/// is read by chunks into a single buffer, which holds a copy of the
+/// whole module. Prefer a path to avoid this copy.
+///
 /// ## Security
 ///
 /// Before the code is compiled, it will be validated using the store
@@ -33,17 +62,44 @@ use std::convert::TryInto;
 ///
 /// # Let's compile WebAssembly from WAT.
 /// module = Module(store, '(module)')
+///
+/// # Let's compile WebAssembly from a file.
+/// from pathlib import Path
+///
+/// module = Module(store, Path('tests/tests.wasm'))
+///
+/// with open('tests/tests.wasm', 'rb') as file:
+///     module = Module(store, file)
 /// ```
-#[pyclass(unsendable)]
-#[text_signature = "(store, bytes)"]
+///
+/// To avoid compiling the same module again and again, e.g. on every
+/// process start, a cache can be used. See `cache.FileSystemCache`.
+#[pyclass]
+#[text_signature = "(store, bytes, cache)"]
 pub struct Module {
     inner: wasmer::Module,
+    parameter_names: Arc<ParameterNames>,
+    metered: bool,
+    instance_limit: Option<Arc<InstanceLimit>>,
+    header: Header,
 }
 
 impl Module {
     pub(crate) fn inner(&self) -> &wasmer::Module {
         &self.inner
     }
+
+    pub(crate) fn parameter_names(&self) -> &Arc<ParameterNames> {
+        &self.parameter_names
+    }
+
+    pub(crate) fn is_metered(&self) -> bool {
+        self.metered
+    }
+
+    pub(crate) fn instance_limit(&self) -> Option<&Arc<InstanceLimit>> {
+        self.instance_limit.as_ref()
+    }
 }
 
 #[pymethods]
@@ -52,42 +108,111 @@ impl Module {
     /// in the `Store`.
     ///
     /// This validation is normally pretty fast and checks the enabled
-    /// WebAssembly features in the `Store` engine to assure deterministic
-    /// validation of the `Module`.
+    /// WebAssembly features in the `Store` engine to assure
+    /// deterministic validation of the `Module`. Other WebAssembly
+    /// proposals can be chosen with `features`, an `engine.Features`
+    /// object.
+    ///
+    /// The module can be given in any of the forms accepted by
+    /// `Module`, including WAT.
+    ///
+    /// By default, it returns whether the module is valid. If
+    /// `raise_error` is true, it raises a `errors.ValidationError`
+    /// exception if the module is invalid instead, which holds the
+    /// `reason`, the byte `offset` and the `section` (if any) of the
+    /// error.
     ///
     /// ## Example
     ///
     /// ```py
-    /// from wasmer import Store, Module
+    /// from wasmer import engine, errors, Store, Module
     ///
     /// assert Module.validate(Store(), wasm_bytes)
+    /// assert Module.validate(Store(), '(module)')
+    ///
+    /// try:
+    ///     Module.validate(
+    ///         Store(),
+    ///         '(module (func (result i32 i32) i32.const 1 i32.const 2))',
+    ///         features=engine.Features(multi_value=False),
+    ///         raise_error=True,
+    ///     )
+    /// except errors.ValidationError as error:
+    ///     assert error.section == 'type'
     /// ```
-    #[text_signature = "(bytes)"]
+    #[text_signature = "(store, bytes, features, raise_error)"]
     #[staticmethod]
-    fn validate(store: &Store, bytes: &PyAny) -> bool {
-        match bytes.downcast::<PyBytes>() {
-            Ok(bytes) => wasmer::Module::validate(store.inner(), bytes.as_bytes()).is_ok(),
-            _ => false,
+    #[args(features = "None", raise_error = "false")]
+    fn validate(
+        py: Python,
+        store: &Store,
+        bytes: &PyAny,
+        features: Option<&Features>,
+        raise_error: bool,
+    ) -> PyResult<bool> {
+        let bytes = ModuleBytes::read(py, bytes)?;
+        let features = features.copied().unwrap_or_else(|| store.features());
+
+        match validation::validate(py, &bytes, &features) {
+            Ok(()) => Ok(true),
+            Err(error) if raise_error => Err(error),
+            Err(_) => Ok(false),
         }
     }
 
     #[new]
-    fn new(store: &Store, bytes: &PyAny) -> PyResult<Self> {
-        let store = store.inner();
-
-        // Read the bytes as if there were real bytes or a WAT string.
-        let module = if let Ok(bytes) = bytes.downcast::<PyBytes>() {
-            wasmer::Module::new(store, bytes.as_bytes())
-        } else if let Ok(string) = bytes.downcast::<PyString>() {
-            wasmer::Module::new(store, string.to_string()?.as_bytes())
-        } else {
-            return Err(to_py_err::<TypeError, _>(
-                "`Module` accepts Wasm bytes or a WAT string",
-            ));
+    fn new(
+        store: &Store,
+        bytes: &PyAny,
+        cache: Option<&PyCell<FileSystemCache>>,
+    ) -> PyResult<Self> {
+        let py = bytes.py();
+        let bytes = ModuleBytes::read(py, bytes)?;
+
+        // Convert the text format once, for both the compiler and the
+        // parameter names reader.
+        let bytes = wat::parse_bytes(&bytes).map_err(|error| {
+            compile_error_to_py_err(
+                py,
+                wasmer::CompileError::Wasm(wasmer::WasmError::Generic(format!(
+                    "Error when converting wat: {}",
+                    error
+                ))),
+            )
+        })?;
+
+        let compile = || {
+            store
+                .compile(&bytes)
+                .map_err(|error| compile_error_to_py_err(py, error))
+        };
+
+        let parameter_names = names::parameter_names(&bytes);
+
+        let module = match cache {
+            Some(cache) => {
+                let cache = cache.borrow();
+
+                match cache.load(store, &bytes) {
+                    Some(module) => module,
+                    None => {
+                        let module = compile()?;
+                        cache.store(py, store, &bytes, &module, &parameter_names)?;
+
+                        module
+                    }
+                }
+            }
+
+            None => compile()?,
         };
 
         Ok(Module {
-            inner: module.map_err(to_py_err::<RuntimeError, _>)?,
+            inner: module,
+            parameter_names: Arc::new(parameter_names),
+            metered: store.is_metered(),
+            instance_limit: store.instance_limit().cloned(),
+            header: Header::new(store),
         })
     }
 
@@ -139,7 +264,13 @@ impl Module {
     /// See the `ExportType` class to learn more.
     #[getter]
     fn exports(&self) -> PyResult<Vec<types::ExportType>> {
-        self.inner.exports().map(TryInto::try_into).collect()
+        // The deferred start function is not a real export, see
+        // `Exports`.
+        self.inner
+            .exports()
+            .filter(|export| export.name() != START_FUNCTION)
+            .map(TryInto::try_into)
+            .collect()
     }
 
     /// Returns a list of `ImportType` objects, which represents all
@@ -192,6 +323,11 @@ impl Module {
     /// Serializes a module into a binary representation that the
     /// `Engine` can later process via `Module.deserialize`.
     ///
+    /// The artifact produced by the engine is wrapped in an envelope
+    /// describing the environment it has been compiled for (Wasmer
+    /// version, engine, compiler, target), along with a hash to
+    /// detect corrupted data.
+    ///
     /// ## Examples
     ///
     /// ```py
@@ -205,12 +341,14 @@ impl Module {
     /// ```
     #[text_signature = "($self)"]
     fn serialize<'p>(&self, py: Python<'p>) -> PyResult<&'p PyBytes> {
+        let artifact = self
+            .inner
+            .serialize()
+            .map_err(to_py_err::<RuntimeError, _>)?;
+
         Ok(PyBytes::new(
             py,
-            self.inner
-                .serialize()
-                .map_err(to_py_err::<RuntimeError, _>)?
-                .as_slice(),
+            &serialization::seal(&self.header, &self.parameter_names, &artifact),
         ))
     }
 
@@ -219,16 +357,21 @@ impl Module {
     /// **Note**: the module has to be serialized before with the
     /// `serialize` method.
     ///
-    /// ## Safety
-    ///
-    /// This function is inherently **unsafe** as the provided bytes:
+    /// Before being loaded, the bytes are checked: a
+    /// `wasmer.errors.DeserializeError` exception is raised if they
+    /// are corrupted or truncated, or if they have been serialized by
+    /// another version of Wasmer, another engine, for another target,
+    /// with or without metering (see `engine.Metering`) unlike the
+    /// store, or with other WebAssembly proposals enabled (see
+    /// `engine.Features`).
     ///
-    /// 1. Are going to be deserialized directly into Rust objects.
-    /// 2. Contains the function assembly bodies and, if intercepted,
-    ///    a malicious actor could inject code into executable
-    ///    memory.
+    /// ## Safety
     ///
-    /// And as such, the `deserialize` method is unsafe.
+    /// The hash only detects accidental corruptions. It does not
+    /// protect against a malicious actor, who can forge a valid
+    /// envelope: the bytes contain the function assembly bodies
+    /// that are loaded into executable memory. Only deserialize bytes
+    /// from a trusted source.
     ///
     /// ## Example
     ///
@@ -256,9 +399,196 @@ impl Module {
     #[text_signature = "($self, bytes)"]
     #[staticmethod]
     fn deserialize(store: &Store, bytes: &PyBytes) -> PyResult<Self> {
-        let module = unsafe { wasmer::Module::deserialize(store.inner(), bytes.as_bytes()) }
-            .map_err(to_py_err::<RuntimeError, _>)?;
+        Self::raw_deserialize(store, bytes.as_bytes())
+    }
+
+    /// Deserializes a serialized module from a file, like
+    /// `Module.deserialize`, with the same checks.
+    ///
+    /// The file is memory-mapped instead of being read in a Python
+    /// `bytes` object, which avoids a copy of large artifacts. It
+    /// works with both the JIT and the Native engines.
+    ///
+    /// ## Safety
+    ///
+    /// See `Module.deserialize`. In addition, the file must not be
+    /// modified while the module is being deserialized.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    /// import os
+    /// import tempfile
+    ///
+    /// store = Store()
+    /// module = Module(store, '(module)')
+    ///
+    /// path = os.path.join(tempfile.mkdtemp(), 'module.wasmer')
+    ///
+    /// with open(path, 'wb') as file:
+    ///     file.write(module.serialize())
+    ///
+    /// module = Module.deserialize_from_file(store, path)
+    ///
+    /// assert isinstance(module, Module)
+    /// ```
+    #[text_signature = "(store, path)"]
+    #[staticmethod]
+    fn deserialize_from_file(py: Python, store: &Store, path: &PyAny) -> PyResult<Self> {
+        let artifact = ModuleBytes::map(py, path)?;
+
+        Self::raw_deserialize(store, &artifact)
+    }
+}
+
+impl Module {
+    fn raw_deserialize(store: &Store, bytes: &[u8]) -> PyResult<Self> {
+        let header = Header::new(store);
+        let (parameter_names, artifact) = serialization::open(&header, bytes)?;
+        let module = unsafe { wasmer::Module::deserialize(store.inner(), artifact) }
+            .map_err(to_py_err::<DeserializeError, _>)?;
+
+        Ok(Module {
+            inner: module,
+            parameter_names: Arc::new(parameter_names),
+            metered: store.is_metered(),
+            instance_limit: store.instance_limit().cloned(),
+            header,
+        })
+    }
+}
+
+/// Converts a path-like object (`str`, `bytes` or `os.PathLike`) to a
+/// path. On Unix, paths are bytes, so the path is encoded with
+/// `os.fsencode`, which keeps the paths that are not valid UTF-8.
+#[cfg(unix)]
+fn fspath(py: Python, path: &PyAny) -> PyResult<PathBuf> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let path = py
+        .import("os")?
+        .call1("fsencode", (path,))?
+        .downcast::<PyBytes>()?;
+
+    Ok(OsStr::from_bytes(path.as_bytes()).into())
+}
+
+/// Converts a path-like object (`str`, `bytes` or `os.PathLike`) to a
+/// path, with `os.fsdecode`.
+#[cfg(not(unix))]
+fn fspath(py: Python, path: &PyAny) -> PyResult<PathBuf> {
+    Ok(py
+        .import("os")?
+        .call1("fsdecode", (path,))?
+        .extract::<String>()?
+        .into())
+}
+
+/// The bytes of a module, read from any of the inputs accepted by
+/// `Module`, without being copied when possible.
+enum ModuleBytes<'p> {
+    Borrowed(&'p [u8]),
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+    Buffer(PyBuffer),
+}
+
+impl<'p> ModuleBytes<'p> {
+    /// Size of the chunks read from file-like objects.
+    const CHUNK_SIZE: usize = 1024 * 1024;
+
+    /// Maps the file at the given path in memory. An empty file
+    /// cannot be mapped, so it is read as empty bytes.
+    fn map(py: Python, path: &PyAny) -> PyResult<Self> {
+        let file = File::open(fspath(py, path)?)?;
+
+        if file.metadata()?.len() == 0 {
+            return Ok(Self::Owned(Vec::new()));
+        }
+
+        Ok(Self::Mapped(unsafe { Mmap::map(&file)? }))
+    }
+
+    fn read(py: Python<'p>, bytes: &'p PyAny) -> PyResult<Self> {
+        // Real bytes, or a WAT string.
+        if let Ok(bytes) = bytes.downcast::<PyBytes>() {
+            return Ok(Self::Borrowed(bytes.as_bytes()));
+        }
+
+        if let Ok(string) = bytes.downcast::<PyString>() {
+            return Ok(Self::Owned(string.to_string()?.into_owned().into_bytes()));
+        }
+
+        // A path.
+        let builtins = py.import("builtins")?;
+        let path_like = py.import("os")?.getattr("PathLike")?;
+
+        if builtins
+            .call1("isinstance", (bytes, path_like))?
+            .is_true()?
+        {
+            return Self::map(py, bytes);
+        }
+
+        // A readable file-like object. The compiler takes a slice,
+        // so the chunks are accumulated: only the intermediate
+        // Python `bytes` object is avoided, not the copy.
+        if bytes.hasattr("read")? {
+            let mut buffer = Vec::new();
+
+            loop {
+                let chunk = bytes.call_method1("read", (Self::CHUNK_SIZE,))?;
+
+                if let Ok(chunk) = chunk.downcast::<PyBytes>() {
+                    buffer.extend_from_slice(chunk.as_bytes());
+                } else if let Ok(chunk) = chunk.downcast::<PyString>() {
+                    buffer.extend_from_slice(chunk.to_string()?.as_bytes());
+                } else {
+                    return Err(to_py_err::<TypeError, _>(
+                        "The `read` method of the file-like object must return bytes or a string",
+                    ));
+                }
 
-        Ok(Module { inner: module })
+                if chunk.len()? == 0 {
+                    break;
+                }
+            }
+
+            return Ok(Self::Owned(buffer));
+        }
+
+        // An object implementing the buffer protocol.
+        if let Ok(buffer) = PyBuffer::get(py, bytes) {
+            if !buffer.is_c_contiguous() {
+                return Err(to_py_err::<TypeError, _>(
+                    "`Module` accepts contiguous buffers only",
+                ));
+            }
+
+            return Ok(Self::Buffer(buffer));
+        }
+
+        Err(to_py_err::<TypeError, _>(
+            "`Module` accepts Wasm bytes, a WAT string, a path, a buffer or a file-like object",
+        ))
+    }
+}
+
+impl Deref for ModuleBytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Borrowed(bytes) => bytes,
+            Self::Owned(bytes) => bytes,
+            Self::Mapped(mmap) => mmap,
+            // The buffer is contiguous, and is not released as long
+            // as `self` is alive. The GIL is held while the module
+            // is compiled, so the buffer cannot be resized meanwhile.
+            Self::Buffer(buffer) => unsafe {
+                slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes())
+            },
+        }
     }
 }
@@ -0,0 +1,196 @@
+use crate::wasmer_inner::wasmer::wasmparser::{
+    ExternalKind, ImportSectionEntryType, Name, NameSectionReader, Parser, Payload, Result, TypeDef,
+};
+use std::{collections::HashMap, convert::TryInto, str};
+
+/// The parameter names of the exported functions of a module, indexed
+/// by export name. A parameter has no name if the name section of the
+/// module does not define one.
+pub(crate) type ParameterNames = HashMap<String, Vec<Option<String>>>;
+
+/// Reads the parameter names of the exported functions of a
+/// WebAssembly module, from its name section.
+///
+/// This is a best-effort reader: if the module is malformed, or has
+/// no name section, no name is returned. The module is validated
+/// later by the compiler anyway.
+pub(crate) fn parameter_names(bytes: &[u8]) -> ParameterNames {
+    read_parameter_names(bytes).unwrap_or_default()
+}
+
+fn read_parameter_names(bytes: &[u8]) -> Result<ParameterNames> {
+    // Number of parameters for each type, if it is a function type.
+    let mut type_arities = Vec::new();
+
+    // Type index for each function (imported functions first).
+    let mut function_types = Vec::new();
+
+    // Exported functions, with their function index.
+    let mut exports = Vec::new();
+
+    // Local names, indexed by function index, then by local index.
+    let mut local_names = HashMap::<u32, HashMap<u32, String>>::new();
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        match payload? {
+            Payload::TypeSection(reader) => {
+                for ty in reader {
+                    type_arities.push(match ty? {
+                        TypeDef::Func(function_type) => Some(function_type.params.len()),
+                        _ => None,
+                    });
+                }
+            }
+
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let ImportSectionEntryType::Function(type_index) = import?.ty {
+                        function_types.push(type_index);
+                    }
+                }
+            }
+
+            Payload::FunctionSection(reader) => {
+                for type_index in reader {
+                    function_types.push(type_index?);
+                }
+            }
+
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+
+                    if let ExternalKind::Function = export.kind {
+                        exports.push((export.field.to_string(), export.index));
+                    }
+                }
+            }
+
+            Payload::CustomSection {
+                name: "name",
+                data,
+                data_offset,
+            } => read_local_names(NameSectionReader::new(data, data_offset)?, &mut local_names)?,
+
+            _ => (),
+        }
+    }
+
+    Ok(exports
+        .into_iter()
+        .filter_map(|(name, function_index)| {
+            let type_index = *function_types.get(function_index as usize)?;
+            let arity = (*type_arities.get(type_index as usize)?)?;
+            let names = local_names.get(&function_index);
+
+            Some((
+                name,
+                (0..arity as u32)
+                    .map(|local_index| names.and_then(|names| names.get(&local_index)).cloned())
+                    .collect(),
+            ))
+        })
+        .collect())
+}
+
+fn read_local_names(
+    reader: NameSectionReader,
+    local_names: &mut HashMap<u32, HashMap<u32, String>>,
+) -> Result<()> {
+    for name in reader {
+        if let Name::Local(locals) = name? {
+            for function in locals.get_function_local_reader()? {
+                let function = function?;
+                let names = local_names.entry(function.func_index).or_default();
+
+                for naming in function.get_map()? {
+                    let naming = naming?;
+
+                    names.insert(naming.index, naming.name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes parameter names, so that they can be stored along a
+/// serialized module, which has lost its name section. See
+/// `serialization::seal`.
+pub(crate) fn encode(parameter_names: &ParameterNames) -> Vec<u8> {
+    fn write_u32(bytes: &mut Vec<u8>, value: usize) {
+        bytes.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+
+    fn write_string(bytes: &mut Vec<u8>, string: &str) {
+        write_u32(bytes, string.len());
+        bytes.extend_from_slice(string.as_bytes());
+    }
+
+    let mut bytes = Vec::new();
+    write_u32(&mut bytes, parameter_names.len());
+
+    for (function_name, names) in parameter_names {
+        write_string(&mut bytes, function_name);
+        write_u32(&mut bytes, names.len());
+
+        for name in names {
+            match name {
+                Some(name) => {
+                    bytes.push(1);
+                    write_string(&mut bytes, name);
+                }
+
+                None => bytes.push(0),
+            }
+        }
+    }
+
+    bytes
+}
+
+/// Decodes parameter names encoded by `encode`.
+pub(crate) fn decode(mut bytes: &[u8]) -> Option<ParameterNames> {
+    fn take<'a>(bytes: &mut &'a [u8], length: usize) -> Option<&'a [u8]> {
+        if length > bytes.len() {
+            return None;
+        }
+
+        let (head, tail) = bytes.split_at(length);
+        *bytes = tail;
+
+        Some(head)
+    }
+
+    fn read_u32(bytes: &mut &[u8]) -> Option<u32> {
+        Some(u32::from_le_bytes(take(bytes, 4)?.try_into().ok()?))
+    }
+
+    fn read_string(bytes: &mut &[u8]) -> Option<String> {
+        let length = read_u32(bytes)? as usize;
+
+        str::from_utf8(take(bytes, length)?).ok().map(String::from)
+    }
+
+    let mut parameter_names = ParameterNames::new();
+
+    for _ in 0..read_u32(&mut bytes)? {
+        let function_name = read_string(&mut bytes)?;
+        let names = (0..read_u32(&mut bytes)?)
+            .map(|_| match take(&mut bytes, 1)? {
+                [0] => Some(None),
+                [1] => Some(Some(read_string(&mut bytes)?)),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+
+        parameter_names.insert(function_name, names);
+    }
+
+    if !bytes.is_empty() {
+        return None;
+    }
+
+    Some(parameter_names)
+}
@@ -0,0 +1,214 @@
+use crate::{
+    errors::{to_py_err, DeserializeError},
+    names::{self, ParameterNames},
+    store::Store,
+    wasmer_inner::wasmer_cache,
+};
+use pyo3::prelude::*;
+use std::{convert::TryInto, str};
+
+const MAGIC: &[u8] = b"\0wasmer-python\0";
+const FORMAT_VERSION: u32 = 1;
+const HASH_LENGTH: usize = 64;
+
+/// Describes the environment an artifact has been compiled for.
+#[derive(Clone)]
+pub(crate) struct Header {
+    wasmer_version: String,
+    engine: String,
+    compiler: String,
+    triple: String,
+    cpu_features: String,
+    metering: String,
+    features: String,
+}
+
+impl Header {
+    pub(crate) fn new(store: &Store) -> Self {
+        let target = store.inner().engine().target();
+
+        Self {
+            wasmer_version: env!("WASMER_VERSION").to_string(),
+            engine: store.engine_name().to_string(),
+            compiler: store
+                .compiler_name()
+                .map_or_else(|| "headless".to_string(), ToString::to_string),
+            triple: target.triple().to_string(),
+            cpu_features: format!("{:?}", target.cpu_features()),
+            metering: if store.is_metered() {
+                "enabled"
+            } else {
+                "disabled"
+            }
+            .to_string(),
+            features: format!("{:?}", store.features()),
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, &String); 7] {
+        [
+            ("wasmer_version", &self.wasmer_version),
+            ("engine", &self.engine),
+            ("compiler", &self.compiler),
+            ("triple", &self.triple),
+            ("cpu_features", &self.cpu_features),
+            ("metering", &self.metering),
+            ("features", &self.features),
+        ]
+    }
+
+    pub(crate) fn encode(&self) -> String {
+        self.fields()
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect()
+    }
+
+    fn decode(header: &str) -> Option<Self> {
+        let mut values = header.lines().map(|line| {
+            let mut key_value = line.splitn(2, '=');
+
+            (key_value.next(), key_value.next())
+        });
+        let mut next = |expected_key| match values.next()? {
+            (Some(key), Some(value)) if key == expected_key => Some(value.to_string()),
+            _ => None,
+        };
+
+        Some(Self {
+            wasmer_version: next("wasmer_version")?,
+            engine: next("engine")?,
+            compiler: next("compiler")?,
+            triple: next("triple")?,
+            cpu_features: next("cpu_features")?,
+            metering: next("metering")?,
+            features: next("features")?,
+        })
+    }
+
+    /// Checks that an artifact compiled for `self` can be loaded in
+    /// an environment described by `expected`. The compiler does not
+    /// matter, since a headless engine can load any artifact.
+    fn check(&self, expected: &Self) -> PyResult<()> {
+        for ((key, found), (_, expected)) in self.fields().iter().zip(expected.fields().iter()) {
+            if *key != "compiler" && found != expected {
+                return Err(to_py_err::<DeserializeError, _>(format!(
+                    "The module has been serialized for the {} `{}`, but the store has `{}`",
+                    key.replace('_', " "),
+                    found,
+                    expected
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn hash(header: &[u8], parameter_names: &[u8], artifact: &[u8]) -> String {
+    wasmer_cache::Hash::generate(&[header, parameter_names, artifact].concat()).to_string()
+}
+
+/// Wraps an artifact produced by the engine in an envelope:
+///
+/// ```text
+/// magic (`\0wasmer-python\0`)
+/// format version (u32, little-endian)
+/// header length (u32, little-endian)
+/// header (UTF-8, `key=value` lines)
+/// parameter names length (u32, little-endian)
+/// parameter names (see `names::encode`)
+/// hash of the header, the parameter names and the artifact (64 hexadecimal digits)
+/// artifact
+/// ```
+///
+/// The header describes the environment the artifact has been
+/// compiled for, and the hash detects corrupted or truncated data,
+/// so that the artifact is never loaded in an incompatible or
+/// corrupted state. The parameter names of the exported functions
+/// are kept, since the artifact has no name section.
+pub(crate) fn seal(header: &Header, parameter_names: &ParameterNames, artifact: &[u8]) -> Vec<u8> {
+    let header = header.encode();
+    let parameter_names = names::encode(parameter_names);
+    let mut bytes = Vec::with_capacity(
+        MAGIC.len() + 12 + header.len() + parameter_names.len() + HASH_LENGTH + artifact.len(),
+    );
+
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(&(parameter_names.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&parameter_names);
+    bytes.extend_from_slice(hash(header.as_bytes(), &parameter_names, artifact).as_bytes());
+    bytes.extend_from_slice(artifact);
+
+    bytes
+}
+
+/// Checks an envelope against the environment described by
+/// `expected`, and returns the parameter names and the artifact it
+/// contains.
+pub(crate) fn open<'a>(expected: &Header, bytes: &'a [u8]) -> PyResult<(ParameterNames, &'a [u8])> {
+    let corrupted = || to_py_err::<DeserializeError, _>("The serialized module is corrupted");
+
+    if !bytes.starts_with(MAGIC) {
+        return Err(to_py_err::<DeserializeError, _>(
+            "The bytes are not a serialized module",
+        ));
+    }
+
+    let bytes = &bytes[MAGIC.len()..];
+
+    let (format_version, bytes) = read_u32(bytes).ok_or_else(corrupted)?;
+
+    if format_version != FORMAT_VERSION {
+        return Err(to_py_err::<DeserializeError, _>(format!(
+            "The serialization format version {} is not supported, expected {}",
+            format_version, FORMAT_VERSION
+        )));
+    }
+
+    let (header_length, bytes) = read_u32(bytes).ok_or_else(corrupted)?;
+    let header_length = header_length as usize;
+
+    if bytes.len() < header_length {
+        return Err(corrupted());
+    }
+
+    let (header, bytes) = bytes.split_at(header_length);
+
+    let (parameter_names_length, bytes) = read_u32(bytes).ok_or_else(corrupted)?;
+    let parameter_names_length = parameter_names_length as usize;
+
+    if bytes.len() < parameter_names_length + HASH_LENGTH {
+        return Err(corrupted());
+    }
+
+    let (parameter_names, bytes) = bytes.split_at(parameter_names_length);
+    let (expected_hash, artifact) = bytes.split_at(HASH_LENGTH);
+
+    if hash(header, parameter_names, artifact).as_bytes() != expected_hash {
+        return Err(corrupted());
+    }
+
+    str::from_utf8(header)
+        .ok()
+        .and_then(Header::decode)
+        .ok_or_else(corrupted)?
+        .check(expected)?;
+
+    let parameter_names = names::decode(parameter_names).ok_or_else(corrupted)?;
+
+    Ok((parameter_names, artifact))
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    if bytes.len() < 4 {
+        return None;
+    }
+
+    let (head, tail) = bytes.split_at(4);
+
+    Some((u32::from_le_bytes(head.try_into().ok()?), tail))
+}
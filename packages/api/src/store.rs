@@ -1,8 +1,10 @@
 use crate::{
     errors::to_py_err,
+    tunables::{InstanceLimit, Limits},
     wasmer_inner::{wasmer, wasmer_engines as engines},
 };
 use pyo3::{exceptions::TypeError, prelude::*};
+use std::sync::Arc;
 
 /// The store represents all global state that can be manipulated by
 /// WebAssembly programs. It consists of the runtime representation of
@@ -43,41 +45,109 @@ use pyo3::{exceptions::TypeError, prelude::*};
 /// `compiler_compiler_cranelift`, `compiler_compiler_llvm`,
 /// `compiler_compiler_singlepass`, otherwise it will run in headless
 /// mode.
+///
+/// Resource limits (memory pages, table elements, instances etc.) can
+/// be set with a `Limits` object:
+///
+/// ```py
+/// from wasmer import Store, Limits
+///
+/// store = Store(limits=Limits(memory_pages=16))
+/// ```
 #[pyclass]
-#[text_signature = "(engine)"]
+#[text_signature = "(engine, limits)"]
 pub struct Store {
     inner: wasmer::Store,
     engine_name: String,
     compiler_name: Option<String>,
+    metered: bool,
+    instance_limit: Option<Arc<InstanceLimit>>,
+    features: engines::Features,
+    compilation_lock: engines::CompilationLock,
+    fingerprint: String,
 }
 
 impl Store {
     pub fn inner(&self) -> &wasmer::Store {
         &self.inner
     }
+
+    /// Whether the engine injects metering in the compiled modules,
+    /// see `engine.Metering`.
+    pub(crate) fn is_metered(&self) -> bool {
+        self.metered
+    }
+
+    /// The limit of living instances, shared by all the modules
+    /// compiled with the store, see `Limits`.
+    pub(crate) fn instance_limit(&self) -> Option<&Arc<InstanceLimit>> {
+        self.instance_limit.as_ref()
+    }
+
+    /// Describes the configuration of the store that has an impact
+    /// on the compiled code (engine, compiler, metering, features,
+    /// some limits), e.g. to key a cache.
+    pub(crate) fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Compiles a module with the engine. The compilations of an
+    /// engine are serialized, since its middlewares keep per-module
+    /// state while compiling.
+    pub(crate) fn compile(&self, bytes: &[u8]) -> Result<wasmer::Module, wasmer::CompileError> {
+        let _compilation = self
+            .compilation_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        wasmer::Module::from_binary(&self.inner, bytes)
+    }
+
+    /// The WebAssembly proposals enabled by the engine, see
+    /// `engine.Features`.
+    pub(crate) fn features(&self) -> engines::Features {
+        self.features
+    }
+}
+
+fn new_store(
+    engine: &(dyn wasmer::Engine + Send + Sync),
+    limits: Option<&Limits>,
+) -> wasmer::Store {
+    match limits {
+        Some(limits) => wasmer::Store::new_with_tunables(engine, limits.tunables(engine)),
+        None => wasmer::Store::new(engine),
+    }
 }
 
 #[pymethods]
 impl Store {
     #[new]
-    fn new(py: Python, engine: Option<&PyAny>) -> PyResult<Self> {
-        let (inner, engine_name, compiler_name) = match engine {
+    fn new(py: Python, engine: Option<&PyAny>, limits: Option<&Limits>) -> PyResult<Self> {
+        let (inner, engine_name, compiler_name, metering, features, compilation_lock) = match engine
+        {
             Some(engine) => {
                 if let Ok(jit) = engine.downcast::<PyCell<engines::JIT>>() {
                     let jit = jit.borrow();
 
                     (
-                        wasmer::Store::new(jit.inner()),
+                        new_store(jit.inner(), limits),
                         engines::JIT::name(),
                         jit.compiler_name().cloned(),
+                        jit.metering().cloned(),
+                        *jit.features(),
+                        jit.compilation_lock().clone(),
                     )
                 } else if let Ok(native) = engine.downcast::<PyCell<engines::Native>>() {
                     let native = native.borrow();
 
                     (
-                        wasmer::Store::new(native.inner()),
+                        new_store(native.inner(), limits),
                         engines::Native::name(),
                         native.compiler_name().cloned(),
+                        native.metering().cloned(),
+                        *native.features(),
+                        native.compilation_lock().clone(),
                     )
                 } else {
                     return Err(to_py_err::<TypeError, _>("Unknown engine"));
@@ -99,30 +169,49 @@ impl Store {
                     .ok();
 
                 let target = None;
-                let engine = engines::JIT::raw_new(compiler, target)?;
+                let metering = None;
+                let features = None;
+                let engine = engines::JIT::raw_new(compiler, target, metering, features)?;
 
                 (
-                    wasmer::Store::new(engine.inner()),
+                    new_store(engine.inner(), limits),
                     engines::JIT::name(),
                     engine.compiler_name().cloned(),
+                    engine.metering().cloned(),
+                    *engine.features(),
+                    engine.compilation_lock().clone(),
                 )
             }
         };
 
+        let fingerprint = format!(
+            "engine={};compiler={:?};metering={:?};features={:?};limits={:?}",
+            engine_name,
+            compiler_name,
+            metering,
+            features,
+            limits.cloned().unwrap_or_default().fingerprint(),
+        );
+
         Ok(Self {
             inner,
             engine_name: engine_name.to_string(),
             compiler_name,
+            metered: metering.is_some(),
+            instance_limit: limits.and_then(Limits::instance_limit),
+            features,
+            compilation_lock,
+            fingerprint,
         })
     }
 
     #[getter]
-    fn engine_name(&self) -> &String {
+    pub(crate) fn engine_name(&self) -> &String {
         &self.engine_name
     }
 
     #[getter]
-    fn compiler_name(&self) -> Option<&String> {
+    pub(crate) fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
 }
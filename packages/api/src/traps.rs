@@ -0,0 +1,209 @@
+use crate::{errors::to_py_err, wasmer_inner::wasmer};
+use pyo3::{class::basic::PyObjectProtocol, exceptions::ValueError, prelude::*};
+use std::slice;
+
+#[derive(Copy, Clone)]
+#[repr(u8)]
+pub enum TrapCode {
+    StackOverflow = 1,
+    HeapAccessOutOfBounds = 2,
+    HeapMisaligned = 3,
+    TableAccessOutOfBounds = 4,
+    OutOfBounds = 5,
+    IndirectCallToNull = 6,
+    BadSignature = 7,
+    IntegerOverflow = 8,
+    IntegerDivisionByZero = 9,
+    BadConversionToInteger = 10,
+    UnreachableCodeReached = 11,
+    Interrupt = 12,
+    UnalignedAtomic = 13,
+}
+
+impl TrapCode {
+    pub fn iter() -> slice::Iter<'static, TrapCode> {
+        static VARIANTS: [TrapCode; 13] = [
+            TrapCode::StackOverflow,
+            TrapCode::HeapAccessOutOfBounds,
+            TrapCode::HeapMisaligned,
+            TrapCode::TableAccessOutOfBounds,
+            TrapCode::OutOfBounds,
+            TrapCode::IndirectCallToNull,
+            TrapCode::BadSignature,
+            TrapCode::IntegerOverflow,
+            TrapCode::IntegerDivisionByZero,
+            TrapCode::BadConversionToInteger,
+            TrapCode::UnreachableCodeReached,
+            TrapCode::Interrupt,
+            TrapCode::UnalignedAtomic,
+        ];
+
+        VARIANTS.iter()
+    }
+}
+
+impl From<&TrapCode> for &'static str {
+    fn from(value: &TrapCode) -> Self {
+        match value {
+            TrapCode::StackOverflow => "STACK_OVERFLOW",
+            TrapCode::HeapAccessOutOfBounds => "HEAP_ACCESS_OUT_OF_BOUNDS",
+            TrapCode::HeapMisaligned => "HEAP_MISALIGNED",
+            TrapCode::TableAccessOutOfBounds => "TABLE_ACCESS_OUT_OF_BOUNDS",
+            TrapCode::OutOfBounds => "OUT_OF_BOUNDS",
+            TrapCode::IndirectCallToNull => "INDIRECT_CALL_TO_NULL",
+            TrapCode::BadSignature => "BAD_SIGNATURE",
+            TrapCode::IntegerOverflow => "INTEGER_OVERFLOW",
+            TrapCode::IntegerDivisionByZero => "INTEGER_DIVISION_BY_ZERO",
+            TrapCode::BadConversionToInteger => "BAD_CONVERSION_TO_INTEGER",
+            TrapCode::UnreachableCodeReached => "UNREACHABLE_CODE_REACHED",
+            TrapCode::Interrupt => "INTERRUPT",
+            TrapCode::UnalignedAtomic => "UNALIGNED_ATOMIC",
+        }
+    }
+}
+
+impl ToPyObject for TrapCode {
+    fn to_object(&self, py: Python) -> PyObject {
+        (*self as u8).into_py(py)
+    }
+}
+
+impl IntoPy<PyObject> for TrapCode {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'source> FromPyObject<'source> for TrapCode {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        let variant = u8::extract(obj)?;
+
+        Ok(match variant {
+            1 => Self::StackOverflow,
+            2 => Self::HeapAccessOutOfBounds,
+            3 => Self::HeapMisaligned,
+            4 => Self::TableAccessOutOfBounds,
+            5 => Self::OutOfBounds,
+            6 => Self::IndirectCallToNull,
+            7 => Self::BadSignature,
+            8 => Self::IntegerOverflow,
+            9 => Self::IntegerDivisionByZero,
+            10 => Self::BadConversionToInteger,
+            11 => Self::UnreachableCodeReached,
+            12 => Self::Interrupt,
+            13 => Self::UnalignedAtomic,
+            _ => {
+                return Err(to_py_err::<ValueError, _>(
+                    "Failed to extract `TrapCode` from `PyAny`",
+                ))
+            }
+        })
+    }
+}
+
+impl From<wasmer::TrapCode> for TrapCode {
+    fn from(value: wasmer::TrapCode) -> Self {
+        match value {
+            wasmer::TrapCode::StackOverflow => Self::StackOverflow,
+            wasmer::TrapCode::HeapAccessOutOfBounds => Self::HeapAccessOutOfBounds,
+            wasmer::TrapCode::HeapMisaligned => Self::HeapMisaligned,
+            wasmer::TrapCode::TableAccessOutOfBounds => Self::TableAccessOutOfBounds,
+            wasmer::TrapCode::OutOfBounds => Self::OutOfBounds,
+            wasmer::TrapCode::IndirectCallToNull => Self::IndirectCallToNull,
+            wasmer::TrapCode::BadSignature => Self::BadSignature,
+            wasmer::TrapCode::IntegerOverflow => Self::IntegerOverflow,
+            wasmer::TrapCode::IntegerDivisionByZero => Self::IntegerDivisionByZero,
+            wasmer::TrapCode::BadConversionToInteger => Self::BadConversionToInteger,
+            wasmer::TrapCode::UnreachableCodeReached => Self::UnreachableCodeReached,
+            wasmer::TrapCode::Interrupt => Self::Interrupt,
+            wasmer::TrapCode::UnalignedAtomic => Self::UnalignedAtomic,
+        }
+    }
+}
+
+/// Represents a frame of a WebAssembly stack trace. It is built by
+/// the `wasmer.errors.Trap.frames` attribute.
+///
+/// The frames are ordered from the most recent call (where the trap
+/// happened) to the oldest one.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import errors, Store, Module, Instance
+///
+/// module = Module(
+///     Store(),
+///     """
+///     (module $crashing_module
+///       (func $crash (export "crash")
+///         unreachable))
+///     """
+/// )
+/// instance = Instance(module)
+///
+/// try:
+///     instance.exports.crash()
+/// except errors.Trap as trap:
+///     assert trap.code == errors.TrapCode.UNREACHABLE_CODE_REACHED
+///
+///     frame = trap.frames[0]
+///
+///     assert isinstance(frame, errors.FrameInfo)
+///     assert frame.module_name == 'crashing_module'
+///     assert frame.function_index == 0
+///     assert frame.function_name == 'crash'
+/// ```
+#[pyclass]
+pub struct FrameInfo {
+    /// The name of the module in which the frame lives. If the module
+    /// has no name, `<module>` is used.
+    #[pyo3(get)]
+    pub module_name: String,
+
+    /// The index of the function, in the module's function index
+    /// space.
+    #[pyo3(get)]
+    pub function_index: u32,
+
+    /// The name of the function, if any, as defined in the name
+    /// section of the module.
+    #[pyo3(get)]
+    pub function_name: Option<String>,
+
+    /// The offset of the trapping instruction relative to the start
+    /// of the function.
+    #[pyo3(get)]
+    pub function_offset: usize,
+
+    /// The offset of the trapping instruction relative to the start
+    /// of the module.
+    #[pyo3(get)]
+    pub module_offset: usize,
+}
+
+impl From<&wasmer::FrameInfo> for FrameInfo {
+    fn from(value: &wasmer::FrameInfo) -> Self {
+        Self {
+            module_name: value.module_name().to_string(),
+            function_index: value.func_index(),
+            function_name: value.function_name().map(ToString::to_string),
+            function_offset: value.func_offset(),
+            module_offset: value.module_offset(),
+        }
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for FrameInfo {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "FrameInfo(module_name={:?}, function_index={}, function_name={:?}, function_offset={:#x}, module_offset={:#x})",
+            self.module_name,
+            self.function_index,
+            self.function_name,
+            self.function_offset,
+            self.module_offset,
+        ))
+    }
+}
@@ -0,0 +1,312 @@
+use crate::wasmer_inner::wasmer::{
+    self,
+    vm::{self, MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition},
+    BaseTunables, MemoryType, Pages, TableType, Tunables,
+};
+use pyo3::prelude::*;
+use std::{
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Resource limits for a `Store`.
+///
+/// The limits apply to all the memories, tables and instances created
+/// with the store. All the limits are optional; a missing limit means
+/// the engine's default applies.
+///
+/// * `memory_pages` caps the number of pages a memory can have. The
+///   maximum size of memories declaring a bigger maximum, or no
+///   maximum, is lowered to this limit, so that `Memory.grow` fails
+///   beyond it. A memory whose minimum exceeds it cannot be created,
+/// * `table_elements` caps the number of elements a table can have,
+///   similarly,
+/// * `instances` caps the number of living instances of the modules
+///   compiled with the store. An instance is living as long as the
+///   `Instance` object, its `Exports`, or any object coming from its
+///   exports (functions, memories and their views, tables, globals)
+///   is alive, or its `InterruptHandle`,
+/// * `static_memory_bound` is the maximum number of pages of a memory
+///   to be allocated statically, i.e. with its maximum size reserved
+///   upfront,
+/// * `static_memory_offset_guard_size` and
+///   `dynamic_memory_offset_guard_size` are the sizes, in bytes, of
+///   the guard regions following static and dynamic memories.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, Memory, MemoryType, Limits
+///
+/// store = Store(limits=Limits(memory_pages=16, instances=8))
+/// memory = Memory(store, MemoryType(1, shared=False))
+///
+/// memory.grow(15)
+///
+/// try:
+///     memory.grow(1)
+/// except RuntimeError:
+///     pass
+/// ```
+#[pyclass]
+#[text_signature = "(/, memory_pages, table_elements, instances, static_memory_bound, static_memory_offset_guard_size, dynamic_memory_offset_guard_size)"]
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    /// The maximum number of pages of a memory, if any.
+    #[pyo3(get)]
+    memory_pages: Option<u32>,
+
+    /// The maximum number of elements of a table, if any.
+    #[pyo3(get)]
+    table_elements: Option<u32>,
+
+    /// The maximum number of living instances, if any.
+    #[pyo3(get)]
+    instances: Option<usize>,
+
+    /// The maximum number of pages of a static memory, if any.
+    #[pyo3(get)]
+    static_memory_bound: Option<u32>,
+
+    /// The size in bytes of the guard region of static memories, if
+    /// any.
+    #[pyo3(get)]
+    static_memory_offset_guard_size: Option<u64>,
+
+    /// The size in bytes of the guard region of dynamic memories, if
+    /// any.
+    #[pyo3(get)]
+    dynamic_memory_offset_guard_size: Option<u64>,
+}
+
+impl Limits {
+    /// Builds the tunables enforcing the limits, based on the
+    /// default tunables of the engine.
+    pub(crate) fn tunables(&self, engine: &dyn wasmer::Engine) -> LimitingTunables {
+        let mut base = BaseTunables::for_target(engine.target());
+
+        if let Some(static_memory_bound) = self.static_memory_bound {
+            base.static_memory_bound = Pages(static_memory_bound);
+        }
+
+        if let Some(static_memory_offset_guard_size) = self.static_memory_offset_guard_size {
+            base.static_memory_offset_guard_size = static_memory_offset_guard_size;
+        }
+
+        if let Some(dynamic_memory_offset_guard_size) = self.dynamic_memory_offset_guard_size {
+            base.dynamic_memory_offset_guard_size = dynamic_memory_offset_guard_size;
+        }
+
+        LimitingTunables {
+            base,
+            memory_pages: self.memory_pages.map(Pages),
+            table_elements: self.table_elements,
+        }
+    }
+
+    /// Describes the limits that have an impact on the compiled code,
+    /// i.e. on the memory styles, e.g. to key a cache. The other
+    /// limits only apply at runtime.
+    pub(crate) fn fingerprint(&self) -> String {
+        format!(
+            "memory_pages={:?},static_memory_bound={:?},static_memory_offset_guard_size={:?},dynamic_memory_offset_guard_size={:?}",
+            self.memory_pages,
+            self.static_memory_bound,
+            self.static_memory_offset_guard_size,
+            self.dynamic_memory_offset_guard_size,
+        )
+    }
+
+    pub(crate) fn instance_limit(&self) -> Option<Arc<InstanceLimit>> {
+        self.instances
+            .map(|maximum| Arc::new(InstanceLimit::new(maximum)))
+    }
+}
+
+#[pymethods]
+impl Limits {
+    #[new]
+    fn new(
+        memory_pages: Option<u32>,
+        table_elements: Option<u32>,
+        instances: Option<usize>,
+        static_memory_bound: Option<u32>,
+        static_memory_offset_guard_size: Option<u64>,
+        dynamic_memory_offset_guard_size: Option<u64>,
+    ) -> Self {
+        Self {
+            memory_pages,
+            table_elements,
+            instances,
+            static_memory_bound,
+            static_memory_offset_guard_size,
+            dynamic_memory_offset_guard_size,
+        }
+    }
+}
+
+/// Tunables lowering the maximum size of memories and tables to the
+/// limits, and refusing to create them if their minimum size exceeds
+/// the limits.
+pub(crate) struct LimitingTunables {
+    base: BaseTunables,
+    memory_pages: Option<Pages>,
+    table_elements: Option<u32>,
+}
+
+impl LimitingTunables {
+    fn adjust_memory(&self, memory_type: &MemoryType) -> Result<MemoryType, MemoryError> {
+        let limit = match self.memory_pages {
+            Some(limit) => limit,
+            None => return Ok(*memory_type),
+        };
+
+        if memory_type.minimum > limit {
+            return Err(MemoryError::Generic(format!(
+                "The memory minimum of {} pages exceeds the limit of {} pages",
+                memory_type.minimum.0, limit.0
+            )));
+        }
+
+        let mut adjusted = *memory_type;
+        adjusted.maximum = Some(
+            memory_type
+                .maximum
+                .map_or(limit, |maximum| maximum.min(limit)),
+        );
+
+        Ok(adjusted)
+    }
+
+    fn adjust_table(&self, table_type: &TableType) -> Result<TableType, String> {
+        let limit = match self.table_elements {
+            Some(limit) => limit,
+            None => return Ok(*table_type),
+        };
+
+        if table_type.minimum > limit {
+            return Err(format!(
+                "The table minimum of {} elements exceeds the limit of {} elements",
+                table_type.minimum, limit
+            ));
+        }
+
+        let mut adjusted = *table_type;
+        adjusted.maximum = Some(
+            table_type
+                .maximum
+                .map_or(limit, |maximum| maximum.min(limit)),
+        );
+
+        Ok(adjusted)
+    }
+}
+
+impl Tunables for LimitingTunables {
+    fn memory_style(&self, memory_type: &MemoryType) -> MemoryStyle {
+        // An invalid memory type is rejected when the memory is
+        // created.
+        let adjusted = self.adjust_memory(memory_type).unwrap_or(*memory_type);
+
+        self.base.memory_style(&adjusted)
+    }
+
+    fn table_style(&self, table_type: &TableType) -> TableStyle {
+        self.base.table_style(table_type)
+    }
+
+    fn create_host_memory(
+        &self,
+        memory_type: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        self.base
+            .create_host_memory(&self.adjust_memory(memory_type)?, style)
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        memory_type: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        self.base.create_vm_memory(
+            &self.adjust_memory(memory_type)?,
+            style,
+            vm_definition_location,
+        )
+    }
+
+    fn create_host_table(
+        &self,
+        table_type: &TableType,
+        style: &TableStyle,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base
+            .create_host_table(&self.adjust_table(table_type)?, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        table_type: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_vm_table(
+            &self.adjust_table(table_type)?,
+            style,
+            vm_definition_location,
+        )
+    }
+}
+
+/// Counts the living instances, shared by a `Store`, its modules and
+/// their instances.
+pub(crate) struct InstanceLimit {
+    maximum: usize,
+    count: AtomicUsize,
+}
+
+impl InstanceLimit {
+    fn new(maximum: usize) -> Self {
+        Self {
+            maximum,
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn maximum(&self) -> usize {
+        self.maximum
+    }
+
+    /// Counts a new instance. It returns `None` if the limit is
+    /// reached, in which case the instance must not be created.
+    pub(crate) fn acquire(self: &Arc<Self>) -> Option<InstanceSlot> {
+        let maximum = self.maximum;
+
+        self.count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                if count < maximum {
+                    Some(count + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()
+            .map(|_| InstanceSlot(self.clone()))
+    }
+}
+
+/// An instance counted by an `InstanceLimit`. The instance is
+/// uncounted when the slot is dropped, i.e. when the exports of the
+/// instance are dropped, see `Exports`.
+pub(crate) struct InstanceSlot(Arc<InstanceLimit>);
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        self.0.count.fetch_sub(1, Ordering::SeqCst);
+    }
+}
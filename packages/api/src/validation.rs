@@ -0,0 +1,89 @@
+use crate::{
+    engines::Features,
+    errors::{to_py_err_with_attributes, ValidationError},
+    wasmer_inner::wasmer::wasmparser::{self, Parser, Payload, SectionReader},
+};
+use pyo3::prelude::*;
+
+/// Validates a module, in the binary or the text format, against the
+/// given proposals.
+///
+/// The error is a `ValidationError` holding the `reason`, the byte
+/// `offset` and the `section` (if any) of the error.
+pub(crate) fn validate(py: Python, bytes: &[u8], features: &Features) -> PyResult<()> {
+    let bytes = wat::parse_bytes(bytes).map_err(|error| {
+        let reason = error.to_string();
+
+        to_py_err_with_attributes::<ValidationError, _>(
+            py,
+            &reason,
+            &[
+                ("reason", reason.to_object(py)),
+                ("offset", py.None()),
+                ("section", py.None()),
+            ],
+        )
+    })?;
+
+    let mut validator = wasmparser::Validator::new();
+    validator.wasm_features(features.wasm_features());
+
+    validator.validate_all(&bytes).map_err(|error| {
+        let reason = error.message();
+        let offset = error.offset();
+        let section = section_at(&bytes, offset);
+        let message = match section {
+            Some(section) => format!(
+                "{} (at offset {}, in the {} section)",
+                reason, offset, section
+            ),
+            None => format!("{} (at offset {})", reason, offset),
+        };
+
+        to_py_err_with_attributes::<ValidationError, _>(
+            py,
+            message,
+            &[
+                ("reason", reason.to_object(py)),
+                ("offset", offset.to_object(py)),
+                ("section", section.to_object(py)),
+            ],
+        )
+    })
+}
+
+/// Returns the name of the section containing `offset`, if any. The
+/// header of a section, i.e. its identifier and its size, belongs to
+/// the section.
+fn section_at(bytes: &[u8], offset: usize) -> Option<&'static str> {
+    for payload in Parser::new(0).parse_all(bytes) {
+        let (section, end) = match payload.ok()? {
+            Payload::Version { range, .. } => (None, range.end),
+            Payload::TypeSection(reader) => (Some("type"), reader.range().end),
+            Payload::ImportSection(reader) => (Some("import"), reader.range().end),
+            Payload::FunctionSection(reader) => (Some("function"), reader.range().end),
+            Payload::TableSection(reader) => (Some("table"), reader.range().end),
+            Payload::MemorySection(reader) => (Some("memory"), reader.range().end),
+            Payload::GlobalSection(reader) => (Some("global"), reader.range().end),
+            Payload::ExportSection(reader) => (Some("export"), reader.range().end),
+            Payload::StartSection { range, .. } => (Some("start"), range.end),
+            Payload::ElementSection(reader) => (Some("element"), reader.range().end),
+            Payload::DataCountSection { range, .. } => (Some("data_count"), range.end),
+            Payload::CodeSectionStart { range, .. } => (Some("code"), range.end),
+            Payload::DataSection(reader) => (Some("data"), reader.range().end),
+            Payload::CustomSection {
+                data_offset, data, ..
+            } => (Some("custom"), data_offset + data.len()),
+            Payload::UnknownSection { range, .. } => (None, range.end),
+            _ => continue,
+        };
+
+        // Sections are visited in order, so the first one ending
+        // after `offset` contains it.
+        if offset < end {
+            return section;
+        }
+    }
+
+    None
+}
@@ -1,8 +1,21 @@
-use crate::wasmer_inner::{wasmer, wasmer_types::NativeWasmType};
+use crate::{
+    errors::to_py_err,
+    exports::ExportsInner,
+    externals::Function,
+    types::Type,
+    wasmer_inner::{wasmer, wasmer_types::NativeWasmType},
+};
 use pyo3::{
-    class::basic::PyObjectProtocol,
+    buffer::PyBuffer,
+    class::basic::{CompareOp, PyObjectProtocol},
+    exceptions::{TypeError, ValueError},
     prelude::*,
-    types::{PyFloat, PyLong},
+    types::{PyFloat, PyLong, PyTuple},
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
 };
 
 pub trait NativeFromPyAny {
@@ -47,8 +60,159 @@ impl NativeFromPyAny for u128 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyLong>()?.extract::<Self::Native>()
+        to_v128(any, None)
+    }
+}
+
+/// The interpretations of a `v128` value as lanes.
+#[derive(Clone, Copy)]
+enum Shape {
+    I8x16,
+    I16x8,
+    I32x4,
+    I64x2,
+    F32x4,
+    F64x2,
+}
+
+impl Shape {
+    fn parse(shape: &str) -> PyResult<Self> {
+        Ok(match shape {
+            "i8x16" => Self::I8x16,
+            "i16x8" => Self::I16x8,
+            "i32x4" => Self::I32x4,
+            "i64x2" => Self::I64x2,
+            "f32x4" => Self::F32x4,
+            "f64x2" => Self::F64x2,
+            _ => {
+                return Err(to_py_err::<ValueError, _>(format!(
+                    "The shape `{}` is not supported; expected `i8x16`, `i16x8`, `i32x4`, `i64x2`, `f32x4` or `f64x2`",
+                    shape
+                )))
+            }
+        })
+    }
+
+    /// Infers the shape from the lanes: integers or floats, and the
+    /// number of lanes.
+    fn infer(lanes: &PyTuple) -> PyResult<Self> {
+        let floats = !lanes.is_empty()
+            && lanes
+                .iter()
+                .all(|lane| lane.is_instance::<PyFloat>().unwrap_or(false));
+
+        Ok(match (floats, lanes.len()) {
+            (false, 16) => Self::I8x16,
+            (false, 8) => Self::I16x8,
+            (false, 4) => Self::I32x4,
+            (false, 2) => Self::I64x2,
+            (true, 4) => Self::F32x4,
+            (true, 2) => Self::F64x2,
+            (_, length) => {
+                return Err(to_py_err::<ValueError, _>(format!(
+                    "Cannot infer the shape of a `v128` value from {} lanes",
+                    length
+                )))
+            }
+        })
+    }
+
+    fn lane_count(self) -> usize {
+        128 / self.lane_bits()
+    }
+
+    fn lane_bits(self) -> usize {
+        match self {
+            Self::I8x16 => 8,
+            Self::I16x8 => 16,
+            Self::I32x4 | Self::F32x4 => 32,
+            Self::I64x2 | Self::F64x2 => 64,
+        }
+    }
+
+    /// Converts a lane to its bits.
+    fn lane(self, lane: &PyAny) -> PyResult<u128> {
+        Ok(match self {
+            Self::F32x4 => lane.extract::<f32>()?.to_bits().into(),
+            Self::F64x2 => lane.extract::<f64>()?.to_bits().into(),
+            _ => {
+                let bits = self.lane_bits();
+                let value = lane.extract::<i128>()?;
+
+                // Both signed and unsigned integers are accepted.
+                if value < -(1 << (bits - 1)) || value >= 1 << bits {
+                    return Err(to_py_err::<ValueError, _>(format!(
+                        "The lane `{}` does not fit in {} bits",
+                        value, bits
+                    )));
+                }
+
+                (value as u128) & (u128::MAX >> (128 - bits))
+            }
+        })
+    }
+}
+
+/// Converts a Python object to a `v128` value. The object can be:
+///
+/// * an integer,
+/// * 16 bytes, in little-endian order, as `bytes` or any object
+///   implementing the buffer protocol (e.g. `bytearray` or
+///   `memoryview`),
+/// * a tuple of lanes, interpreted with `shape` (`i8x16`, `i16x8`,
+///   `i32x4`, `i64x2`, `f32x4` or `f64x2`). Without `shape`, it is
+///   inferred from the number of lanes, and whether they are floats.
+pub(crate) fn to_v128(any: &PyAny, shape: Option<&str>) -> PyResult<u128> {
+    if let Ok(lanes) = any.downcast::<PyTuple>() {
+        let shape = match shape {
+            Some(shape) => Shape::parse(shape)?,
+            None => Shape::infer(lanes)?,
+        };
+
+        if lanes.len() != shape.lane_count() {
+            return Err(to_py_err::<ValueError, _>(format!(
+                "A `v128` value has {} lanes with this shape, given {}",
+                shape.lane_count(),
+                lanes.len()
+            )));
+        }
+
+        return lanes
+            .iter()
+            .enumerate()
+            .try_fold(0u128, |value, (index, lane)| {
+                Ok(value | (shape.lane(lane)? << (index * shape.lane_bits())))
+            });
+    }
+
+    if shape.is_some() {
+        return Err(to_py_err::<TypeError, _>(
+            "A shape can only be given with a tuple of lanes",
+        ));
+    }
+
+    // Bytes, or any other object implementing the buffer protocol.
+    if let Ok(buffer) = PyBuffer::get(any.py(), any) {
+        let mut bytes = [0; 16];
+
+        if buffer.len_bytes() != bytes.len() {
+            return Err(to_py_err::<ValueError, _>(format!(
+                "A `v128` value must have 16 bytes, given {}",
+                buffer.len_bytes()
+            )));
+        }
+
+        buffer.copy_to_slice(any.py(), &mut bytes)?;
+
+        return Ok(u128::from_le_bytes(bytes));
     }
+
+    let integer = any.downcast::<PyLong>()?;
+
+    // Negative integers are represented in two's complement.
+    integer
+        .extract::<u128>()
+        .or_else(|_| integer.extract::<i128>().map(|value| value as u128))
 }
 
 pub trait TryFromPyAny {
@@ -67,26 +231,108 @@ impl TryFromPyAny for PyAny {
 }
 
 pub(crate) fn to_wasm_value((any, ty): (&PyAny, wasmer::Type)) -> PyResult<wasmer::Value> {
+    // A `Value` object is used as is, e.g. to keep the bits of a
+    // float.
+    if let Ok(value) = any.downcast::<PyCell<Value>>() {
+        let value = value.borrow().inner().clone();
+
+        if value.ty() != ty {
+            return Err(to_py_err::<TypeError, _>(format!(
+                "The value is of type `Type.{}`, expected `Type.{}`",
+                <&'static str>::from(&Type::from(&value.ty())),
+                <&'static str>::from(&Type::from(&ty)),
+            )));
+        }
+
+        return Ok(value);
+    }
+
     Ok(match ty {
         wasmer::Type::I32 => any.try_from::<i32>()?.to_value(),
         wasmer::Type::I64 => any.try_from::<i64>()?.to_value(),
         wasmer::Type::F32 => any.try_from::<f32>()?.to_value(),
         wasmer::Type::F64 => any.try_from::<f64>()?.to_value(),
         wasmer::Type::V128 => any.try_from::<u128>()?.to_value(),
-        _ => unimplemented!(),
+        wasmer::Type::FuncRef => {
+            if any.is_none() {
+                wasmer::Value::FuncRef(None)
+            } else if let Ok(function) = any.downcast::<PyCell<Function>>() {
+                wasmer::Value::FuncRef(Some(function.borrow().inner().clone()))
+            } else {
+                return Err(to_py_err::<TypeError, _>(
+                    "A `funcref` value must be a `Function` or `None`",
+                ));
+            }
+        }
+        wasmer::Type::ExternRef => {
+            if any.is_none() {
+                wasmer::Value::ExternRef(wasmer::ExternRef::null())
+            } else {
+                // The reference owns a strong reference to the Python
+                // object, which is released when the reference is
+                // dropped.
+                wasmer::Value::ExternRef(wasmer::ExternRef::new(any.to_object(any.py())))
+            }
+        }
     })
 }
 
-pub(crate) fn to_py_object<'p>(py: Python<'p>) -> impl Fn(&wasmer::Value) -> PyObject + 'p {
-    move |value: &wasmer::Value| -> PyObject {
-        match value {
+pub(crate) fn to_py_object<'p>(
+    py: Python<'p>,
+) -> impl Fn(&wasmer::Value) -> PyResult<PyObject> + 'p {
+    to_py_object_from(py, None)
+}
+
+/// Like `to_py_object`, but the functions are attached to `exports`,
+/// i.e. the exports of the instance the values come from (through a
+/// table, a global or the results of a function), so that calling
+/// them behaves like calling the exported functions (metering,
+/// interruption), see `Function::with_exports`.
+pub(crate) fn to_py_object_from<'p>(
+    py: Python<'p>,
+    exports: Option<Arc<ExportsInner>>,
+) -> impl Fn(&wasmer::Value) -> PyResult<PyObject> + 'p {
+    move |value: &wasmer::Value| -> PyResult<PyObject> {
+        Ok(match value {
             wasmer::Value::I32(value) => value.to_object(py),
             wasmer::Value::I64(value) => value.to_object(py),
             wasmer::Value::F32(value) => value.to_object(py),
             wasmer::Value::F64(value) => value.to_object(py),
             wasmer::Value::V128(value) => value.to_object(py),
-            _ => unimplemented!(),
-        }
+            wasmer::Value::FuncRef(Some(function)) => {
+                let function = Function::raw_new(function.clone());
+                let function = match &exports {
+                    Some(exports) => function.with_exports(exports.clone()),
+                    None => function,
+                };
+
+                Py::new(py, function)?.to_object(py)
+            }
+            wasmer::Value::FuncRef(None) => py.None(),
+            wasmer::Value::ExternRef(extern_ref) => {
+                if extern_ref.is_null() {
+                    py.None()
+                } else {
+                    extern_ref
+                        .downcast::<PyObject>()
+                        .ok_or_else(|| {
+                            to_py_err::<TypeError, _>(
+                                "The `externref` value does not hold a Python object",
+                            )
+                        })?
+                        .clone_ref(py)
+                }
+            }
+        })
+    }
+}
+
+/// Like `to_py_object`, but converts the values to `Value` objects.
+pub(crate) fn to_py_value<'p>(
+    py: Python<'p>,
+) -> impl Fn(&wasmer::Value) -> PyResult<PyObject> + 'p {
+    move |value: &wasmer::Value| -> PyResult<PyObject> {
+        Ok(Py::new(py, Value::raw_new(value.clone()))?.to_object(py))
     }
 }
 
@@ -95,22 +341,71 @@ pub(crate) fn to_py_object<'p>(py: Python<'p>) -> impl Fn(&wasmer::Value) -> PyO
 /// Most of the time, the types for WebAssembly values will be
 /// inferred. When it's not possible, the `Value` class is necessary.
 ///
+/// Reference values are represented by Python objects: a `funcref`
+/// is a `Function` object, an `externref` is any Python object, and
+/// `None` is the null reference for both. An `externref` is opaque to
+/// WebAssembly code, and is given back unchanged to the host; the
+/// object is kept alive as long as WebAssembly holds a reference to
+/// it.
+///
 /// ## Example
 ///
 /// ```py
-/// from wasmer import Value
+/// from wasmer import Value, Type
 ///
 /// value = Value.i32(42)
+///
+/// assert value.type == Type.I32
+/// assert value.value == 42
+/// assert value == Value.i32(42)
 /// ```
-#[pyclass(unsendable)]
+///
+/// Values are compared and hashed by type and bits, so that two
+/// floats with the same NaN payload are equal. Two `externref` values
+/// are equal if they reference the same Python object. A non-null
+/// `funcref` value is only equal to itself, and is not hashable.
+///
+/// `Value` objects are also returned instead of Python numbers by
+/// the raw mode of `Function.call`, given to host functions
+/// created with `raw=True`, and returned by `Global.raw_value`, so
+/// that they are not altered, e.g. NaN payloads are kept. It matters
+/// when replaying numeric code deterministically.
+#[pyclass]
 pub struct Value {
     inner: wasmer::Value,
 }
 
 impl Value {
+    pub(crate) fn raw_new(inner: wasmer::Value) -> Self {
+        Self { inner }
+    }
+
     pub(crate) fn inner(&self) -> &wasmer::Value {
         &self.inner
     }
+
+    /// Identifies the value, for comparisons and hashing: its type
+    /// and its bits, or the address of the referenced Python object.
+    /// It is `None` for a non-null `funcref` value, or an `externref`
+    /// value that does not hold a Python object.
+    fn key(&self) -> Option<(u8, u128)> {
+        let ty = Type::from(&self.inner.ty()) as u8;
+        let bits = match &self.inner {
+            wasmer::Value::I32(value) => u128::from(*value as u32),
+            wasmer::Value::I64(value) => u128::from(*value as u64),
+            wasmer::Value::F32(value) => u128::from(value.to_bits()),
+            wasmer::Value::F64(value) => u128::from(value.to_bits()),
+            wasmer::Value::V128(value) => *value,
+            wasmer::Value::ExternRef(extern_ref) if extern_ref.is_null() => 0,
+            wasmer::Value::ExternRef(extern_ref) => {
+                extern_ref.downcast::<PyObject>()?.as_ptr() as usize as u128
+            }
+            wasmer::Value::FuncRef(None) => 0,
+            wasmer::Value::FuncRef(Some(_)) => return None,
+        };
+
+        Some((ty, bits))
+    }
 }
 
 #[pymethods]
@@ -185,20 +480,167 @@ impl Value {
 
     /// Build a WebAssembly `v128` value.
     ///
+    /// The value can be an integer, 16 bytes (in little-endian
+    /// order, i.e. as in memory, from any object implementing the
+    /// buffer protocol), or a tuple of lanes. The `shape` of
+    /// the lanes is `i8x16`, `i16x8`, `i32x4`, `i64x2`, `f32x4` or
+    /// `f64x2`; if absent, it is inferred from the number of lanes,
+    /// and whether they are floats. Integer lanes can be signed or
+    /// unsigned. The first lane is the least significant one.
+    ///
     /// ## Example
     ///
     /// ```py
     /// from wasmer import Value
     ///
     /// value = Value.v128(42)
+    /// value = Value.v128(b'\x2a' + b'\x00' * 15)
+    /// value = Value.v128(bytearray(b'\x2a' + b'\x00' * 15))
+    /// value = Value.v128((42, 0, 0, 0))
+    /// value = Value.v128((42, 0, 0, 0), shape='i32x4')
+    /// value = Value.v128((4.2, 0.0), shape='f64x2')
     /// ```
     #[staticmethod]
-    #[text_signature = "(value)"]
-    fn v128(value: u128) -> Self {
+    #[text_signature = "(value, shape)"]
+    fn v128(value: &PyAny, shape: Option<&str>) -> PyResult<Self> {
+        Ok(Self {
+            inner: wasmer::Value::V128(to_v128(value, shape)?),
+        })
+    }
+
+    /// Build a WebAssembly `funcref` value, from a `Function` object,
+    /// or `None` for the null reference.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Function, Value
+    ///
+    /// def answer() -> int:
+    ///     return 42
+    ///
+    /// value = Value.funcref(Function(Store(), answer))
+    /// null = Value.funcref(None)
+    /// ```
+    #[staticmethod]
+    #[text_signature = "(function)"]
+    fn funcref(function: &PyAny) -> PyResult<Self> {
+        Ok(Self {
+            inner: to_wasm_value((function, wasmer::Type::FuncRef))?,
+        })
+    }
+
+    /// Build a WebAssembly `externref` value, from any Python object,
+    /// or `None` for the null reference.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.externref({'any': 'object'})
+    /// null = Value.externref(None)
+    /// ```
+    #[staticmethod]
+    #[text_signature = "(object)"]
+    fn externref(object: &PyAny) -> PyResult<Self> {
+        Ok(Self {
+            inner: to_wasm_value((object, wasmer::Type::ExternRef))?,
+        })
+    }
+
+    /// Build a WebAssembly `f32` value from its bits, e.g. to keep
+    /// a NaN payload.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.f32_from_bits(0x7fc00001)
+    ///
+    /// assert value.bits == 0x7fc00001
+    /// ```
+    #[staticmethod]
+    #[text_signature = "(bits)"]
+    fn f32_from_bits(bits: u32) -> Self {
         Self {
-            inner: wasmer::Value::V128(value),
+            inner: wasmer::Value::F32(f32::from_bits(bits)),
         }
     }
+
+    /// Build a WebAssembly `f64` value from its bits, e.g. to keep
+    /// a NaN payload.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.f64_from_bits(0x7ff8000000000001)
+    ///
+    /// assert value.bits == 0x7ff8000000000001
+    /// ```
+    #[staticmethod]
+    #[text_signature = "(bits)"]
+    fn f64_from_bits(bits: u64) -> Self {
+        Self {
+            inner: wasmer::Value::F64(f64::from_bits(bits)),
+        }
+    }
+
+    /// The type of the value, as a `Type` member.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value, Type
+    ///
+    /// assert Value.f64(4.2).type == Type.F64
+    /// ```
+    #[getter(type)]
+    fn ty(&self) -> Type {
+        Type::from(&self.inner.ty())
+    }
+
+    /// The value, converted to a Python object as `Function.__call__`
+    /// does, e.g. an `int` for an `i32` value.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.i64(42).value == 42
+    /// ```
+    #[getter]
+    fn value(&self, py: Python) -> PyResult<PyObject> {
+        to_py_object(py)(&self.inner)
+    }
+
+    /// The bits of a numeric value, as an unsigned integer: the two's
+    /// complement representation of integers, or the IEEE 754
+    /// representation of floats.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.i32(-1).bits == 0xffffffff
+    /// assert Value.f32(1.0).bits == 0x3f800000
+    /// ```
+    #[getter]
+    fn bits(&self, py: Python) -> PyResult<PyObject> {
+        Ok(match self.inner {
+            wasmer::Value::I32(value) => (value as u32).into_py(py),
+            wasmer::Value::I64(value) => (value as u64).into_py(py),
+            wasmer::Value::F32(value) => value.to_bits().into_py(py),
+            wasmer::Value::F64(value) => value.to_bits().into_py(py),
+            wasmer::Value::V128(value) => value.into_py(py),
+            _ => return Err(to_py_err::<TypeError, _>("A reference value has no bits")),
+        })
+    }
 }
 
 #[pyproto]
@@ -206,4 +648,38 @@ impl PyObjectProtocol for Value {
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self.inner()))
     }
+
+    fn __richcmp__(&self, other: &PyAny, operator: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+        let other = match other.downcast::<PyCell<Value>>() {
+            Ok(other) => other.borrow(),
+            Err(_) => return Ok(py.NotImplemented()),
+        };
+
+        let equal = match (self.key(), other.key()) {
+            (Some(key), Some(other_key)) => key == other_key,
+            // Not comparable, fall back to the identity.
+            _ => return Ok(py.NotImplemented()),
+        };
+
+        Ok(match operator {
+            CompareOp::Eq => equal.into_py(py),
+            CompareOp::Ne => (!equal).into_py(py),
+            _ => py.NotImplemented(),
+        })
+    }
+
+    fn __hash__(&self) -> PyResult<isize> {
+        let key = self
+            .key()
+            .ok_or_else(|| to_py_err::<TypeError, _>("The value is not hashable"))?;
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+
+        // `-1` is reserved for errors by CPython.
+        Ok(match hasher.finish() as isize {
+            -1 => -2,
+            hash => hash,
+        })
+    }
 }
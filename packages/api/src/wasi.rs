@@ -434,7 +434,7 @@ impl StateBuilder {
 ///
 /// To build it, use `StateBuilder`. See `StateBuilder.finalize` to
 /// learn more.
-#[pyclass(unsendable)]
+#[pyclass]
 pub struct Environment {
     inner: wasmer_wasi::WasiEnv,
 }
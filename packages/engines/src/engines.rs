@@ -1,6 +1,9 @@
-use crate::target_lexicon::Target;
+use crate::{
+    features::Features, interruption::Interruption, metering::Metering,
+    per_module::CompilationLock, start::DeferredStart, target_lexicon::Target,
+};
 use pyo3::{exceptions::RuntimeError, prelude::*};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// JIT engine for Wasmer compilers.
 ///
@@ -11,17 +14,41 @@ use std::sync::Arc;
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
-#[pyclass(unsendable)]
-#[text_signature = "(/, compiler, target)"]
+///
+/// It is also possible to specify a `Metering` configuration to
+/// bound the number of executed operators. It requires a compiler.
+/// With a compiler, the compiled code can be interrupted too, see
+/// `InterruptHandle`.
+///
+/// Finally, it is possible to specify the WebAssembly proposals to
+/// enable with `Features`. Modules using other proposals are rejected
+/// at validation and compilation time.
+#[pyclass]
+#[text_signature = "(/, compiler, target, metering, features)"]
 pub struct JIT {
     inner: wasmer::JITEngine,
     compiler_name: Option<String>,
+    metering: Option<Metering>,
+    features: Features,
+    compilation_lock: CompilationLock,
 }
 
 impl JIT {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        metering: Option<&Metering>,
+        features: Option<&Features>,
+    ) -> PyResult<Self> {
+        let features = features.copied().unwrap_or_default();
         let (inner, compiler_name) = match compiler {
-            None => (wasmer::JIT::headless().engine(), None),
+            None if metering.is_some() => {
+                return Err(RuntimeError::py_err("Metering requires a compiler"))
+            }
+            None => (
+                wasmer::JIT::headless().features(features.inner()).engine(),
+                None,
+            ),
             Some(compiler) => {
                 let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
                 let opaque_compiler_inner_ptr = opaque_compiler
@@ -39,15 +66,23 @@ impl JIT {
                     })?
                 };
 
-                // Let's clone the `OpaqueCompilerInner` so that
-                // whatever happens to its parent `compiler`
-                // Python object, we own a reference to it.
-                let opaque_compiler_inner: OpaqueCompilerInner = opaque_compiler_inner_ref.clone();
+                // Let's take the compiler configuration out of the
+                // `OpaqueCompilerInner`, so that whatever happens to
+                // its parent `compiler` Python object, we own it, and
+                // we can configure it.
+                let mut compiler_config = opaque_compiler_inner_ref.take_compiler_config()?;
+                compiler_config.push_middleware(Arc::new(DeferredStart));
 
-                debug_assert_eq!(Arc::strong_count(&opaque_compiler_inner.compiler_config), 2);
+                if let Some(metering) = metering {
+                    compiler_config.push_middleware(metering.middleware());
+                }
+
+                // After the metering, so that the checks cost no
+                // points.
+                compiler_config.push_middleware(Interruption::middleware());
 
                 let mut engine_builder =
-                    wasmer::JIT::new(opaque_compiler_inner.compiler_config.as_ref());
+                    wasmer::JIT::new(&*compiler_config).features(features.inner());
 
                 if let Some(target) = target {
                     engine_builder = engine_builder.target(target.inner().clone());
@@ -68,6 +103,9 @@ impl JIT {
         Ok(Self {
             inner,
             compiler_name,
+            metering: metering.cloned(),
+            features,
+            compilation_lock: CompilationLock::default(),
         })
     }
 
@@ -82,13 +120,32 @@ impl JIT {
     pub fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    pub fn metering(&self) -> Option<&Metering> {
+        self.metering.as_ref()
+    }
+
+    pub fn features(&self) -> &Features {
+        &self.features
+    }
+
+    /// The lock to hold while compiling a module with this engine,
+    /// see `CompilationLock`.
+    pub fn compilation_lock(&self) -> &CompilationLock {
+        &self.compilation_lock
+    }
 }
 
 #[pymethods]
 impl JIT {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        metering: Option<&Metering>,
+        features: Option<&Features>,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, metering, features)
     }
 }
 
@@ -103,17 +160,43 @@ impl JIT {
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
-#[pyclass(unsendable)]
-#[text_signature = "(/, compiler, target)"]
+///
+/// It is also possible to specify a `Metering` configuration to
+/// bound the number of executed operators. It requires a compiler.
+/// With a compiler, the compiled code can be interrupted too, see
+/// `InterruptHandle`.
+///
+/// Finally, it is possible to specify the WebAssembly proposals to
+/// enable with `Features`. Modules using other proposals are rejected
+/// at validation and compilation time.
+#[pyclass]
+#[text_signature = "(/, compiler, target, metering, features)"]
 pub struct Native {
     inner: wasmer::NativeEngine,
     compiler_name: Option<String>,
+    metering: Option<Metering>,
+    features: Features,
+    compilation_lock: CompilationLock,
 }
 
 impl Native {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        metering: Option<&Metering>,
+        features: Option<&Features>,
+    ) -> PyResult<Self> {
+        let features = features.copied().unwrap_or_default();
         let (inner, compiler_name) = match compiler {
-            None => (wasmer::Native::headless().engine(), None),
+            None if metering.is_some() => {
+                return Err(RuntimeError::py_err("Metering requires a compiler"))
+            }
+            None => (
+                wasmer::Native::headless()
+                    .features(features.inner())
+                    .engine(),
+                None,
+            ),
             Some(compiler) => {
                 let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
                 let opaque_compiler_inner_ptr = opaque_compiler
@@ -131,29 +214,23 @@ impl Native {
                     })?
                 };
 
-                // Let's clone the `OpaqueCompilerInner` so that
-                // whatever happens to its parent `compiler`
-                // Python object, we own a reference to it.
-                let opaque_compiler_inner: OpaqueCompilerInner = opaque_compiler_inner_ref.clone();
-
-                debug_assert_eq!(Arc::strong_count(&opaque_compiler_inner.compiler_config), 2);
+                // Let's take the compiler configuration out of the
+                // `OpaqueCompilerInner`, so that whatever happens to
+                // its parent `compiler` Python object, we own it, and
+                // we can configure it.
+                let mut compiler_config = opaque_compiler_inner_ref.take_compiler_config()?;
+                compiler_config.push_middleware(Arc::new(DeferredStart));
 
-                // Since we've cloned the `OpaqueCompilerInner`
-                // previously, its strong count is equal to
-                // 2. Consequently, we can't get a mutable version
-                // of it, and we need one.
-                //
-                // However, we are ensure the original value won't
-                // be used, since the value exists only in this
-                // block of code. Thus, we believe it is safe to
-                // cast the pointer to a mutable refeference.
+                if let Some(metering) = metering {
+                    compiler_config.push_middleware(metering.middleware());
+                }
 
-                let compiler_config_ptr: *mut dyn wasmer_compiler::CompilerConfig =
-                    Arc::as_ptr(&opaque_compiler_inner.compiler_config) as *mut _;
-                let compiler_config_ref: &mut dyn wasmer_compiler::CompilerConfig =
-                    unsafe { &mut *compiler_config_ptr };
+                // After the metering, so that the checks cost no
+                // points.
+                compiler_config.push_middleware(Interruption::middleware());
 
-                let mut engine_builder = wasmer::Native::new(compiler_config_ref);
+                let mut engine_builder =
+                    wasmer::Native::new(&mut *compiler_config).features(features.inner());
 
                 if let Some(target) = target {
                     engine_builder = engine_builder.target(target.inner().clone());
@@ -174,6 +251,9 @@ impl Native {
         Ok(Self {
             inner,
             compiler_name,
+            metering: metering.cloned(),
+            features,
+            compilation_lock: CompilationLock::default(),
         })
     }
 
@@ -188,19 +268,53 @@ impl Native {
     pub fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    pub fn metering(&self) -> Option<&Metering> {
+        self.metering.as_ref()
+    }
+
+    pub fn features(&self) -> &Features {
+        &self.features
+    }
+
+    /// The lock to hold while compiling a module with this engine,
+    /// see `CompilationLock`.
+    pub fn compilation_lock(&self) -> &CompilationLock {
+        &self.compilation_lock
+    }
 }
 
 #[pymethods]
 impl Native {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        metering: Option<&Metering>,
+        features: Option<&Features>,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, metering, features)
     }
 }
 
-#[derive(Clone)]
+type CompilerConfig = Box<dyn wasmer_compiler::CompilerConfig + Send>;
+
 struct OpaqueCompilerInner {
-    compiler_config: Arc<dyn wasmer_compiler::CompilerConfig + Send + Sync>,
+    compiler_config: Mutex<Option<CompilerConfig>>,
+}
+
+impl OpaqueCompilerInner {
+    /// Takes the compiler configuration, to build an engine with
+    /// it. An opaque compiler is created on-the-fly by
+    /// `into_opaque_compiler` for a single engine, so its
+    /// configuration can only be taken once.
+    fn take_compiler_config(&self) -> PyResult<CompilerConfig> {
+        self.compiler_config
+            .lock()
+            .map_err(|_| RuntimeError::py_err("The opaque compiler is poisoned"))?
+            .take()
+            .ok_or_else(|| RuntimeError::py_err("The opaque compiler has already been used"))
+    }
 }
 
 /// Opaque compiler.
@@ -219,7 +333,7 @@ impl OpaqueCompiler {
     {
         Self {
             inner: OpaqueCompilerInner {
-                compiler_config: Arc::new(compiler_config),
+                compiler_config: Mutex::new(Some(Box::new(compiler_config))),
             },
             compiler_name,
         }
@@ -0,0 +1,140 @@
+use pyo3::{exceptions::ValueError, prelude::*};
+use wasmer::wasmparser::WasmFeatures;
+
+/// The WebAssembly proposals to enable, on top of the MVP.
+///
+/// Each proposal is designated by a boolean flag:
+///
+/// * `simd`, the fixed-width SIMD proposal (`v128`),
+/// * `threads`, the threads and atomics proposal,
+/// * `bulk_memory`, the bulk memory operations proposal,
+/// * `reference_types`, the reference types proposal; it requires
+///   `bulk_memory`,
+/// * `multi_value`, the multi-value proposal,
+/// * `tail_call`, the tail calls proposal.
+///
+/// By default, `bulk_memory` and `multi_value` are enabled, the other
+/// proposals are disabled.
+///
+/// The features are given to an engine, e.g. `engine.JIT(compiler,
+/// features=features)`; the modules are then validated and compiled
+/// against these proposals only. They can also be given to
+/// `Module.validate`.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import engine, Store, Module
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// features = engine.Features(simd=True, threads=False)
+///
+/// assert features.simd
+/// assert features.bulk_memory
+/// assert not features.threads
+///
+/// assert Module.validate(Store(), '(module)', features=features)
+///
+/// # Reject the modules using SIMD.
+/// store = Store(engine.JIT(Compiler, features=engine.Features(simd=False)))
+/// ```
+#[pyclass]
+#[text_signature = "(/, simd, threads, bulk_memory, reference_types, multi_value, tail_call)"]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Features {
+    /// Whether the SIMD proposal is enabled.
+    #[pyo3(get)]
+    simd: bool,
+
+    /// Whether the threads proposal is enabled.
+    #[pyo3(get)]
+    threads: bool,
+
+    /// Whether the bulk memory proposal is enabled.
+    #[pyo3(get)]
+    bulk_memory: bool,
+
+    /// Whether the reference types proposal is enabled.
+    #[pyo3(get)]
+    reference_types: bool,
+
+    /// Whether the multi-value proposal is enabled.
+    #[pyo3(get)]
+    multi_value: bool,
+
+    /// Whether the tail calls proposal is enabled.
+    #[pyo3(get)]
+    tail_call: bool,
+}
+
+impl Features {
+    /// The features of the engine.
+    pub fn inner(&self) -> wasmer::Features {
+        wasmer::Features {
+            simd: self.simd,
+            threads: self.threads,
+            bulk_memory: self.bulk_memory,
+            reference_types: self.reference_types,
+            multi_value: self.multi_value,
+            tail_call: self.tail_call,
+            ..wasmer::Features::default()
+        }
+    }
+
+    /// The proposals to validate modules against.
+    pub fn wasm_features(&self) -> WasmFeatures {
+        WasmFeatures {
+            simd: self.simd,
+            threads: self.threads,
+            bulk_memory: self.bulk_memory,
+            reference_types: self.reference_types,
+            multi_value: self.multi_value,
+            tail_call: self.tail_call,
+            ..WasmFeatures::default()
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            simd: false,
+            threads: false,
+            bulk_memory: true,
+            reference_types: false,
+            multi_value: true,
+            tail_call: false,
+        }
+    }
+}
+
+#[pymethods]
+impl Features {
+    #[new]
+    fn new(
+        simd: Option<bool>,
+        threads: Option<bool>,
+        bulk_memory: Option<bool>,
+        reference_types: Option<bool>,
+        multi_value: Option<bool>,
+        tail_call: Option<bool>,
+    ) -> PyResult<Self> {
+        let default = Self::default();
+        let features = Self {
+            simd: simd.unwrap_or(default.simd),
+            threads: threads.unwrap_or(default.threads),
+            bulk_memory: bulk_memory.unwrap_or(default.bulk_memory),
+            reference_types: reference_types.unwrap_or(default.reference_types),
+            multi_value: multi_value.unwrap_or(default.multi_value),
+            tail_call: tail_call.unwrap_or(default.tail_call),
+        };
+
+        if features.reference_types && !features.bulk_memory {
+            return Err(ValueError::py_err(
+                "The reference types proposal requires the bulk memory proposal",
+            ));
+        }
+
+        Ok(features)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::per_module::PerModule;
+use std::sync::{Arc, Mutex};
+use wasmer::wasmparser::{Operator, Type as WpType, TypeOrFuncType};
+use wasmer_compiler::{
+    FunctionMiddleware, MiddlewareError, MiddlewareReaderState, ModuleMiddleware,
+};
+use wasmer_types::{
+    ExportIndex, GlobalIndex, GlobalInit, GlobalType, LocalFunctionIndex, Mutability, Type,
+};
+use wasmer_vm::ModuleInfo;
+
+/// The name of the global exported by the instances of a module
+/// compiled with interruption checks. The instance is interrupted
+/// when the global is set to 1.
+pub const INTERRUPTED_GLOBAL: &str = "wasmer_python_interrupted";
+
+/// A middleware making the compiled code check whether the instance
+/// has been interrupted, at the beginning of each function and of each
+/// loop iteration. If it is the case, the code traps.
+///
+/// The flag is a global of its own, which is only written by the
+/// host, so that an interruption can never be overwritten by the
+/// guest.
+///
+/// Every module compiled by an engine with a compiler has the checks,
+/// with or without metering.
+#[derive(Debug, Default)]
+pub(crate) struct Interruption {
+    /// The index of the flag in the module being compiled. A new
+    /// middleware is created for each module, see `PerModule`.
+    global_index: Mutex<Option<GlobalIndex>>,
+}
+
+impl Interruption {
+    /// The middleware to compile a module with, created anew for each
+    /// module, see `PerModule`.
+    pub(crate) fn middleware() -> Arc<dyn ModuleMiddleware> {
+        Arc::new(PerModule::new(|| Arc::new(Self::default())))
+    }
+}
+
+impl ModuleMiddleware for Interruption {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionInterruption {
+            global_index: self
+                .global_index
+                .lock()
+                .unwrap()
+                .expect("The module info must be transformed before the functions"),
+            at_start: true,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let global_index = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+
+        module_info.exports.insert(
+            INTERRUPTED_GLOBAL.to_string(),
+            ExportIndex::Global(global_index),
+        );
+
+        *self.global_index.lock().unwrap() = Some(global_index);
+    }
+}
+
+#[derive(Debug)]
+struct FunctionInterruption {
+    global_index: GlobalIndex,
+    at_start: bool,
+}
+
+impl FunctionInterruption {
+    /// `if (global.get $interrupted) { unreachable }`
+    fn check<'a>(&self) -> [Operator<'a>; 4] {
+        [
+            Operator::GlobalGet {
+                global_index: self.global_index.as_u32(),
+            },
+            Operator::If {
+                ty: TypeOrFuncType::Type(WpType::EmptyBlockType),
+            },
+            Operator::Unreachable,
+            Operator::End,
+        ]
+    }
+}
+
+impl FunctionMiddleware for FunctionInterruption {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        if self.at_start {
+            self.at_start = false;
+            state.extend(self.check().iter().cloned());
+        }
+
+        let is_loop = matches!(operator, Operator::Loop { .. });
+
+        state.push_operator(operator);
+
+        // Check at the beginning of the loop body, i.e. at every
+        // iteration.
+        if is_loop {
+            state.extend(self.check().iter().cloned());
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
 mod engines;
+mod features;
+mod interruption;
+mod metering;
+mod per_module;
+mod start;
 mod target_lexicon;
 
 pub use crate::engines::{Native, OpaqueCompiler, JIT};
+pub use crate::features::Features;
+pub use crate::interruption::INTERRUPTED_GLOBAL;
+pub use crate::metering::Metering;
+pub use crate::per_module::CompilationLock;
+pub use crate::start::START_FUNCTION;
 pub use crate::target_lexicon::{CpuFeatures, Target, Triple};
@@ -0,0 +1,106 @@
+use crate::per_module::PerModule;
+use pyo3::prelude::*;
+use std::{collections::BTreeMap, sync::Arc};
+use wasmer::wasmparser::Operator;
+use wasmer_middlewares::Metering as MeteringMiddleware;
+
+/// Metering configuration for an engine.
+///
+/// When an engine is built with a metering configuration, the
+/// compiler injects some instruction-cost accounting in the compiled
+/// code. Each instance starts with `initial_points` points, and each
+/// executed WebAssembly operator consumes some points. When there is
+/// no more points, the execution stops with a
+/// `wasmer.errors.MeteringPointsExhausted` exception.
+///
+/// By default, every operator costs 1 point. The `costs` dictionary
+/// overrides the cost of specific operators, designated by their
+/// name, e.g. `I32Add`, `Call`, `MemoryGrow` etc.
+///
+/// The remaining points of an instance can be read and updated with
+/// `Instance.remaining_points` and `Instance.set_remaining_points`.
+///
+/// Note that metering requires a compiler.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import engine, Store, Module, Instance
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// metering = engine.Metering(initial_points=1000, costs={'Call': 10})
+/// store = Store(engine.JIT(Compiler, metering=metering))
+///
+/// module = Module(store, open('tests/tests.wasm', 'rb').read())
+/// instance = Instance(module)
+///
+/// instance.exports.sum(1, 2)
+///
+/// assert instance.remaining_points < 1000
+/// ```
+#[pyclass]
+#[text_signature = "(initial_points, costs)"]
+#[derive(Clone, Debug)]
+pub struct Metering {
+    /// The number of points each instance starts with.
+    #[pyo3(get)]
+    initial_points: u64,
+    costs: Arc<BTreeMap<String, u64>>,
+}
+
+impl Metering {
+    /// The metering middleware to compile a module with. It is
+    /// created anew for each module, see `PerModule`.
+    pub(crate) fn middleware(&self) -> Arc<dyn wasmer_compiler::ModuleMiddleware> {
+        let initial_points = self.initial_points;
+        let costs = self.costs.clone();
+
+        Arc::new(PerModule::new(move || {
+            let costs = costs.clone();
+
+            Arc::new(MeteringMiddleware::new(
+                initial_points,
+                move |operator: &Operator| -> u64 {
+                    if costs.is_empty() {
+                        return 1;
+                    }
+
+                    costs
+                        .get(operator_name(operator).as_str())
+                        .copied()
+                        .unwrap_or(1)
+                },
+            ))
+        }))
+    }
+}
+
+#[pymethods]
+impl Metering {
+    #[new]
+    fn new(initial_points: u64, costs: Option<BTreeMap<String, u64>>) -> Self {
+        Self {
+            initial_points,
+            costs: Arc::new(costs.unwrap_or_default()),
+        }
+    }
+
+    /// The cost of the operators, indexed by operator name. Operators
+    /// that are absent cost 1 point.
+    #[getter]
+    fn costs(&self) -> BTreeMap<String, u64> {
+        self.costs.as_ref().clone()
+    }
+}
+
+/// Returns the name of an operator, i.e. the name of its variant
+/// without the immediates, like `I32Add` or `Call`.
+fn operator_name(operator: &Operator) -> String {
+    let mut name = format!("{:?}", operator);
+    let length = name
+        .find(|character: char| !character.is_ascii_alphanumeric())
+        .unwrap_or_else(|| name.len());
+    name.truncate(length);
+
+    name
+}
@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+use wasmer_compiler::{FunctionMiddleware, ModuleMiddleware};
+use wasmer_types::LocalFunctionIndex;
+use wasmer_vm::ModuleInfo;
+
+type Middleware = Arc<dyn ModuleMiddleware>;
+
+/// A middleware creating a new instance of another middleware for
+/// each module being compiled.
+///
+/// The middlewares of an engine are given once, to the compiler
+/// configuration, but stateful middlewares (metering, interruption)
+/// keep per-module state (e.g. the index of the globals they inject),
+/// and must not be reused from one module to another. The state of
+/// a module lives from `transform_module_info` to the last
+/// `generate_function_middleware`, so the compilations of an engine
+/// must not overlap, see `CompilationLock`.
+pub(crate) struct PerModule<F>
+where
+    F: Fn() -> Middleware + Send + Sync,
+{
+    new_middleware: F,
+    current: Mutex<Option<Middleware>>,
+}
+
+impl<F> PerModule<F>
+where
+    F: Fn() -> Middleware + Send + Sync,
+{
+    pub(crate) fn new(new_middleware: F) -> Self {
+        Self {
+            new_middleware,
+            current: Mutex::new(None),
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for PerModule<F>
+where
+    F: Fn() -> Middleware + Send + Sync,
+{
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter
+            .debug_struct("PerModule")
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+impl<F> ModuleMiddleware for PerModule<F>
+where
+    F: Fn() -> Middleware + Send + Sync,
+{
+    fn generate_function_middleware(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        self.current
+            .lock()
+            .unwrap()
+            .as_ref()
+            .expect("The module info must be transformed before the functions")
+            .generate_function_middleware(local_function_index)
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let middleware = (self.new_middleware)();
+        middleware.transform_module_info(module_info);
+
+        *self.current.lock().unwrap() = Some(middleware);
+    }
+}
+
+/// A lock serializing the compilations of an engine, shared by all
+/// the stores using it, see `PerModule`.
+pub type CompilationLock = Arc<Mutex<()>>;
@@ -0,0 +1,54 @@
+use wasmer::wasmparser::Operator;
+use wasmer_compiler::{
+    FunctionMiddleware, MiddlewareError, MiddlewareReaderState, ModuleMiddleware,
+};
+use wasmer_types::{ExportIndex, LocalFunctionIndex};
+use wasmer_vm::ModuleInfo;
+
+/// The name under which the start function of a module compiled
+/// with `DeferredStart` is exported.
+pub const START_FUNCTION: &str = "wasmer_python_start";
+
+/// A middleware removing the start function of a module, and
+/// exporting it instead, so that the instance can call it once it is
+/// completely initialized.
+///
+/// Wasmer runs the start function before initializing the
+/// environments of the host functions, so a host function called by
+/// the start function would not have access to its caller instance.
+#[derive(Debug, Default)]
+pub(crate) struct DeferredStart;
+
+impl ModuleMiddleware for DeferredStart {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionDeferredStart)
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        if let Some(start_function) = module_info.start_function.take() {
+            module_info.exports.insert(
+                START_FUNCTION.to_string(),
+                ExportIndex::Function(start_function),
+            );
+        }
+    }
+}
+
+/// The functions are left unchanged.
+#[derive(Debug)]
+struct FunctionDeferredStart;
+
+impl FunctionMiddleware for FunctionDeferredStart {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        state.push_operator(operator);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+from wasmer import cache, engine, Store, Module, Instance, Limits
+from wasmer_compiler_cranelift import Compiler
+import inspect
+import os
+import pytest
+
+here = os.path.dirname(os.path.realpath(__file__))
+TEST_BYTES = open(here + '/tests.wasm', 'rb').read()
+
+def test_is_a_module():
+    assert inspect.ismodule(cache)
+
+def test_file_system_cache(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+
+    assert isinstance(file_system_cache, cache.FileSystemCache)
+    assert file_system_cache.path == str(tmp_path)
+
+def test_file_system_cache_creates_the_directory(tmp_path):
+    path = tmp_path / 'foo' / 'bar'
+    cache.FileSystemCache(str(path))
+
+    assert path.is_dir()
+
+def test_populate_and_load(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+    store = Store()
+
+    module = Module(store, TEST_BYTES, cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 1
+
+    module = Module(store, TEST_BYTES, cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 1
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_key_depends_on_the_bytes(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+    store = Store()
+
+    Module(store, '(module)', cache=file_system_cache)
+    Module(store, '(module (func))', cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 2
+
+def test_key_depends_on_the_store(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+
+    Module(Store(engine.JIT(Compiler)), '(module)', cache=file_system_cache)
+    Module(Store(engine.Native(Compiler)), '(module)', cache=file_system_cache)
+    Module(Store(engine.JIT(Compiler, metering=engine.Metering(100))), '(module)', cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 3
+
+def test_key_depends_on_the_compilation_limits_only(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+
+    Module(Store(engine.JIT(Compiler)), '(module)', cache=file_system_cache)
+    Module(Store(engine.JIT(Compiler), Limits(instances=1, table_elements=8)), '(module)', cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 1
+
+    Module(Store(engine.JIT(Compiler), Limits(memory_pages=16)), '(module)', cache=file_system_cache)
+
+    assert len(os.listdir(str(tmp_path))) == 2
+
+def test_corrupted_artifact(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+    store = Store()
+
+    Module(store, TEST_BYTES, cache=file_system_cache)
+
+    for name in os.listdir(str(tmp_path)):
+        with open(os.path.join(str(tmp_path), name), 'wb') as file:
+            file.write(b'garbage')
+
+    # The corrupted artifact is ignored, and the module compiled again.
+    module = Module(store, TEST_BYTES, cache=file_system_cache)
+
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_artifacts_are_sealed(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+
+    Module(Store(), TEST_BYTES, cache=file_system_cache)
+
+    for name in os.listdir(str(tmp_path)):
+        with open(os.path.join(str(tmp_path), name), 'rb') as file:
+            assert file.read().startswith(b'\0wasmer-python\0')
+
+def test_truncated_artifact(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+    store = Store()
+
+    Module(store, TEST_BYTES, cache=file_system_cache)
+
+    for name in os.listdir(str(tmp_path)):
+        path = os.path.join(str(tmp_path), name)
+
+        with open(path, 'rb') as file:
+            artifact = file.read()
+
+        with open(path, 'wb') as file:
+            file.write(artifact[:len(artifact) // 2])
+
+    # The truncated artifact is ignored, and the module compiled again.
+    module = Module(store, TEST_BYTES, cache=file_system_cache)
+
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_store_is_best_effort(tmp_path):
+    file_system_cache = cache.FileSystemCache(str(tmp_path))
+    store = Store()
+
+    Module(store, TEST_BYTES, cache=file_system_cache)
+
+    # A non-empty directory in place of the artifact makes the rename
+    # of the temporary file fail.
+    for name in os.listdir(str(tmp_path)):
+        path = os.path.join(str(tmp_path), name)
+        os.remove(path)
+        os.makedirs(os.path.join(path, 'occupied'))
+
+    with pytest.warns(RuntimeWarning, match='The module cannot be stored in the cache'):
+        module = Module(store, TEST_BYTES, cache=file_system_cache)
+
+    assert Instance(module).exports.sum(1, 2) == 3
+    assert not any(name.endswith('.tmp') for name in os.listdir(str(tmp_path)))
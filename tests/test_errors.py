@@ -0,0 +1,129 @@
+from wasmer import errors, Store, Module, Instance, ImportObject, Memory, MemoryType
+import inspect
+import os
+import pytest
+
+here = os.path.dirname(os.path.realpath(__file__))
+INVALID_TEST_BYTES = open(here + '/invalid.wasm', 'rb').read()
+
+def test_is_a_module():
+    assert inspect.ismodule(errors)
+
+def test_hierarchy():
+    assert issubclass(errors.CompileError, RuntimeError)
+    assert issubclass(errors.ValidationError, errors.CompileError)
+    assert issubclass(errors.InstantiationError, RuntimeError)
+    assert issubclass(errors.LinkError, errors.InstantiationError)
+    assert issubclass(errors.Trap, RuntimeError)
+    assert issubclass(errors.MeteringPointsExhausted, errors.Trap)
+    assert issubclass(errors.Interrupted, errors.Trap)
+    assert issubclass(errors.MemoryError, RuntimeError)
+
+def test_module_name():
+    assert errors.CompileError.__module__ == 'wasmer.errors'
+    assert errors.Trap.__module__ == 'wasmer.errors'
+    assert repr(errors.LinkError) == "<class 'wasmer.errors.LinkError'>"
+
+def test_compile_error():
+    with pytest.raises(errors.CompileError):
+        Module(Store(), INVALID_TEST_BYTES)
+
+def test_link_error():
+    module = Module(Store(), '(module (import "env" "missing" (func)))')
+
+    with pytest.raises(errors.LinkError) as context_manager:
+        Instance(module)
+
+    exception = context_manager.value
+    assert exception.module == 'env'
+    assert exception.name == 'missing'
+
+def test_link_error_with_an_empty_import_object():
+    module = Module(Store(), '(module (import "env" "missing" (func)))')
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module, ImportObject())
+
+def test_trap():
+    module = Module(Store(), '(module (func (export "crash") unreachable))')
+    instance = Instance(module)
+
+    with pytest.raises(errors.Trap) as context_manager:
+        instance.exports.crash()
+
+    exception = context_manager.value
+    assert isinstance(exception.message, str)
+
+def test_trap_is_a_runtime_error():
+    module = Module(Store(), '(module (func (export "crash") unreachable))')
+    instance = Instance(module)
+
+    with pytest.raises(RuntimeError):
+        instance.exports.crash()
+
+def test_memory_error():
+    memory = Memory(Store(), MemoryType(1, maximum=2, shared=False))
+
+    with pytest.raises(errors.MemoryError) as context_manager:
+        memory.grow(3)
+
+    exception = context_manager.value
+    assert exception.current == 1
+    assert exception.attempted_delta == 3
+
+def test_trap_code():
+    module = Module(Store(), '(module (func (export "crash") unreachable))')
+    instance = Instance(module)
+
+    with pytest.raises(errors.Trap) as context_manager:
+        instance.exports.crash()
+
+    exception = context_manager.value
+    assert exception.code == errors.TrapCode.UNREACHABLE_CODE_REACHED
+
+def test_trap_code_integer_division_by_zero():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "div") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.div_s))
+        """
+    )
+    instance = Instance(module)
+
+    with pytest.raises(errors.Trap) as context_manager:
+        instance.exports.div(1, 0)
+
+    exception = context_manager.value
+    assert exception.code == errors.TrapCode.INTEGER_DIVISION_BY_ZERO
+
+def test_trap_frames():
+    module = Module(
+        Store(),
+        """
+        (module $crashing_module
+          (func $crash
+            unreachable)
+          (func $main (export "main")
+            call $crash))
+        """
+    )
+    instance = Instance(module)
+
+    with pytest.raises(errors.Trap) as context_manager:
+        instance.exports.main()
+
+    frames = context_manager.value.frames
+
+    assert len(frames) == 2
+    assert isinstance(frames[0], errors.FrameInfo)
+    assert frames[0].module_name == 'crashing_module'
+    assert frames[0].function_index == 0
+    assert frames[0].function_name == 'crash'
+    assert isinstance(frames[0].function_offset, int)
+    assert isinstance(frames[0].module_offset, int)
+    assert frames[1].function_index == 1
+    assert frames[1].function_name == 'main'
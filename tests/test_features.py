@@ -0,0 +1,65 @@
+from wasmer import engine, errors, Store, Module
+from wasmer_compiler_cranelift import Compiler
+import pytest
+
+def test_features_default():
+    features = engine.Features()
+
+    assert features.simd == False
+    assert features.threads == False
+    assert features.bulk_memory == True
+    assert features.reference_types == False
+    assert features.multi_value == True
+    assert features.tail_call == False
+
+def test_features():
+    features = engine.Features(simd=True, multi_value=False)
+
+    assert features.simd == True
+    assert features.threads == False
+    assert features.bulk_memory == True
+    assert features.reference_types == False
+    assert features.multi_value == False
+    assert features.tail_call == False
+
+def test_features_reference_types_require_bulk_memory():
+    with pytest.raises(ValueError) as context_manager:
+        engine.Features(reference_types=True, bulk_memory=False)
+
+    exception = context_manager.value
+    assert str(exception) == 'The reference types proposal requires the bulk memory proposal'
+
+SIMD_WAT = '(module (func (export "f") (result v128) v128.const i32x4 1 2 3 4))'
+MULTI_VALUE_WAT = '(module (func (export "f") (result i32 i32) i32.const 1 i32.const 2))'
+
+@pytest.mark.parametrize('engine_class', [engine.JIT, engine.Native])
+def test_engine_rejects_disabled_proposal(engine_class):
+    store = Store(engine_class(Compiler, features=engine.Features(multi_value=False)))
+
+    assert not Module.validate(store, MULTI_VALUE_WAT)
+
+    with pytest.raises(errors.ValidationError):
+        Module(store, MULTI_VALUE_WAT)
+
+@pytest.mark.parametrize('engine_class', [engine.JIT, engine.Native])
+def test_engine_accepts_enabled_proposal(engine_class):
+    store = Store(engine_class(Compiler, features=engine.Features(simd=True)))
+
+    assert Module.validate(store, SIMD_WAT)
+    assert isinstance(Module(store, SIMD_WAT), Module)
+
+def test_engine_default_features():
+    store = Store(engine.JIT(Compiler))
+
+    assert not Module.validate(store, SIMD_WAT)
+    assert Module.validate(store, MULTI_VALUE_WAT)
+
+def test_validate_features_override_store():
+    store = Store(engine.JIT(Compiler, features=engine.Features(simd=True)))
+
+    assert not Module.validate(store, SIMD_WAT, features=engine.Features())
+
+def test_headless_engine_features():
+    store = Store(engine.JIT(features=engine.Features(multi_value=False)))
+
+    assert not Module.validate(store, MULTI_VALUE_WAT)
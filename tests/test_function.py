@@ -1,7 +1,11 @@
 import wasmer
-from wasmer import Instance, Module, Store, Function, FunctionType, Type
+from wasmer import Instance, Module, Store, Function, FunctionType, Type, ImportObject, CallerContext, NativeFunction, Value
+import asyncio
 import os
+import threading
+import time
 import pytest
+from typing import NewType, Tuple
 
 here = os.path.dirname(os.path.realpath(__file__))
 TEST_BYTES = open(here + '/tests.wasm', 'rb').read()
@@ -67,3 +71,745 @@ def test_call_string():
 
 def test_call_void():
     assert instance().exports.void() == None
+
+def host_function_raising(exception):
+    def fail() -> int:
+        raise exception
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "fail" (func $fail (result i32)))
+          (func (export "run") (result i32)
+            call $fail))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"fail": Function(store, fail)})
+
+    return Instance(module, import_object).exports.run
+
+def test_host_function_exception_is_propagated():
+    exception = KeyError('oops')
+
+    with pytest.raises(KeyError) as context_manager:
+        host_function_raising(exception)()
+
+    assert context_manager.value is exception
+
+def test_host_function_exception_keeps_its_cause():
+    cause = ValueError('cause')
+
+    try:
+        raise TypeError('effect') from cause
+    except TypeError as error:
+        exception = error
+
+    with pytest.raises(TypeError) as context_manager:
+        host_function_raising(exception)()
+
+    assert context_manager.value.__cause__ is cause
+
+def test_host_function_exception_keeps_its_traceback():
+    def fail() -> int:
+        raise KeyError('oops')
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "fail" (func $fail (result i32)))
+          (func (export "run") (result i32)
+            call $fail))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"fail": Function(store, fail)})
+
+    with pytest.raises(KeyError) as context_manager:
+        Instance(module, import_object).exports.run()
+
+    assert any(entry.name == 'fail' for entry in context_manager.traceback)
+
+LOG_MODULE = """
+(module
+  (import "env" "log" (func $log (param i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "Hello")
+  (func (export "run")
+    i32.const 0
+    i32.const 5
+    call $log))
+"""
+
+def test_host_function_with_env():
+    def log(context, pointer, length):
+        memory = context.memory.uint8_view(offset=pointer)
+        context.data.append(bytes(memory[0:length]).decode())
+
+    logs = []
+    store = Store()
+    module = Module(store, LOG_MODULE)
+
+    import_object = ImportObject()
+    import_object.register(
+        "env",
+        {
+            "log": Function(store, log, FunctionType([Type.I32, Type.I32], []), env=logs)
+        }
+    )
+
+    instance = Instance(module, import_object)
+    instance.exports.run()
+
+    assert logs == ['Hello']
+
+def test_host_function_with_env_flag():
+    contexts = []
+
+    def log(context, pointer: int, length: int):
+        contexts.append(context)
+
+    store = Store()
+    module = Module(store, LOG_MODULE)
+
+    import_object = ImportObject()
+    import_object.register("env", {"log": Function(store, log, with_env=True)})
+
+    instance = Instance(module, import_object)
+    instance.exports.run()
+
+    assert len(contexts) == 1
+    assert isinstance(contexts[0], CallerContext)
+    assert contexts[0].data == None
+    assert isinstance(contexts[0].exports.run, Function)
+    assert contexts[0].memory.size == 1
+
+def test_host_function_with_annotated_env():
+    def log(context: CallerContext, pointer: int, length: int):
+        pass
+
+    store = Store()
+    function = Function(store, log, with_env=True)
+
+    assert function.type.params == [Type.I32, Type.I32]
+
+def test_caller_context_not_initialized():
+    contexts = []
+
+    def log(context, pointer: int, length: int):
+        contexts.append(context)
+
+    store = Store()
+    function = Function(store, log, with_env=True)
+    function(1, 2)
+
+    with pytest.raises(RuntimeError):
+        contexts[0].exports
+
+def test_caller_context_per_instance():
+    def log(context, pointer: int, length: int):
+        memory = context.memory.uint8_view(offset=pointer)
+        context.data.append(bytes(memory[0:length]).decode())
+
+    logs = []
+    store = Store()
+    module = Module(store, LOG_MODULE)
+
+    import_object = ImportObject()
+    import_object.register("env", {"log": Function(store, log, env=logs)})
+
+    instance1 = Instance(module, import_object)
+    instance2 = Instance(module, import_object)
+    instance2.exports.memory.uint8_view()[0:5] = b'World'
+
+    instance1.exports.run()
+    instance2.exports.run()
+    instance1.exports.run()
+
+    assert logs == ['Hello', 'World', 'Hello']
+
+def test_caller_context_in_start_function():
+    def log(context, pointer: int, length: int):
+        memory = context.memory.uint8_view(offset=pointer)
+        context.data.append(bytes(memory[0:length]).decode())
+
+    logs = []
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "log" (func $log (param i32 i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "Hello")
+          (func $start
+            i32.const 0
+            i32.const 5
+            call $log)
+          (start $start))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"log": Function(store, log, env=logs)})
+
+    instance = Instance(module, import_object)
+
+    assert logs == ['Hello']
+    assert [export.name for export in module.exports] == ['memory']
+    assert len(instance.exports) == 1
+
+def test_constructor_with_marker_types():
+    def function(a: wasmer.i32, b: wasmer.i64, c: wasmer.f32, d: wasmer.f64, e: wasmer.v128) -> wasmer.i64:
+        return 0
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I32, Type.I64, Type.F32, Type.F64, Type.V128]
+    assert type.results == [Type.I64]
+
+def test_constructor_with_type_members():
+    def function(a: Type.I64, b: Type.F64) -> Type.F64:
+        return 0.
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I64, Type.F64]
+    assert type.results == [Type.F64]
+
+def test_constructor_with_new_type():
+    Pointer = NewType('Pointer', wasmer.i32)
+    Length = NewType('Length', int)
+
+    def function(pointer: Pointer, length: Length) -> None:
+        pass
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I32, Type.I32]
+    assert type.results == []
+
+def test_constructor_with_tuple_results():
+    def function(x: wasmer.i64, y: wasmer.f64) -> Tuple[wasmer.f64, wasmer.i64]:
+        return (y, x)
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I64, Type.F64]
+    assert type.results == [Type.F64, Type.I64]
+
+def test_constructor_with_stringified_annotations():
+    def function(x: 'wasmer.i64', y: 'float') -> 'Tuple[int, int]':
+        return (1, 2)
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I64, Type.F32]
+    assert type.results == [Type.I32, Type.I32]
+
+def test_constructor_with_unsupported_annotation():
+    def function(x: str):
+        pass
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Function(Store(), function)
+
+    exception = context_manager.value
+    assert str(exception) == "Type `<class 'str'>` is not a supported type"
+
+def test_multiple_results_from_host_function():
+    def function(x: wasmer.i64, y: wasmer.f64) -> Tuple[wasmer.f64, wasmer.i64]:
+        return (y, x)
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "swap" (func $swap (param i64 f64) (result f64 i64)))
+          (func (export "run") (param i64 f64) (result f64 i64)
+            local.get 0
+            local.get 1
+            call $swap))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"swap": Function(store, function)})
+
+    assert Instance(module, import_object).exports.run(1, 2.5) == (2.5, 1)
+
+def test_constructor_parameters_are_ordered_by_signature():
+    def function(x: wasmer.i64, y: wasmer.f32) -> wasmer.f64:
+        return 0.
+
+    # Mess with the annotations dictionary order.
+    function.__annotations__ = {'return': wasmer.f64, 'y': wasmer.f32, 'x': wasmer.i64}
+
+    type = Function(Store(), function).type
+
+    assert type.params == [Type.I64, Type.F32]
+    assert type.results == [Type.F64]
+
+def test_constructor_with_missing_annotation():
+    def function(x: int, y) -> int:
+        return x
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function)
+
+    exception = context_manager.value
+    assert str(exception) == 'The parameter `y` must have a type annotation'
+
+def test_constructor_with_default_value():
+    def function(x: int, y: int = 1) -> int:
+        return x + y
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function)
+
+    exception = context_manager.value
+    assert str(exception) == 'The parameter `y` cannot have a default value'
+
+def test_constructor_with_variadic_parameters():
+    def function(*args: int) -> int:
+        return sum(args)
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function, FunctionType([Type.I32, Type.I32], [Type.I32]))
+
+    exception = context_manager.value
+    assert str(exception) == 'The parameter `args` cannot be variadic'
+
+def test_constructor_with_keyword_only_parameters():
+    def function(x: int, *, y: int) -> int:
+        return x + y
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function)
+
+    exception = context_manager.value
+    assert str(exception) == 'The parameter `y` cannot be keyword-only'
+
+def test_constructor_with_mismatching_function_type():
+    def function(x, y):
+        return x + y
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function, FunctionType([Type.I32], [Type.I32]))
+
+    exception = context_manager.value
+    assert str(exception) == 'The function has 2 parameter(s), but its type expects 1'
+
+def test_constructor_with_env_without_parameters():
+    def function():
+        pass
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), function, FunctionType([], []), with_env=True)
+
+    exception = context_manager.value
+    assert str(exception) == 'The function must receive the caller context as its first parameter'
+
+SUB_MODULE = """
+(module
+  (func (export "sub") (param $x i32) (param $y i32) (result i32)
+    local.get $x
+    local.get $y
+    i32.sub)
+  (func (export "anonymous_sub") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.sub))
+"""
+
+def sub_instance():
+    return Instance(Module(Store(), SUB_MODULE))
+
+def test_call_too_many_arguments():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1, 2, 3)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function takes 2 argument(s) but 3 were given'
+
+def test_call_missing_arguments():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function is missing argument #1 of type `Type.I32`'
+
+def test_call_invalid_argument_type():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1, 'foo')
+
+    exception = context_manager.value
+    assert str(exception) == 'Argument #1 must be of type `Type.I32`, given `str`'
+
+def test_call_argument_out_of_range():
+    with pytest.raises(OverflowError):
+        instance().exports.sum(1, 2**40)
+
+def test_call_with_keyword_arguments():
+    exports = sub_instance().exports
+
+    assert exports.sub(3, 2) == 1
+    assert exports.sub(3, y=2) == 1
+    assert exports.sub(y=2, x=3) == 1
+
+def test_call_with_keyword_arguments_after_deserialization():
+    store = Store()
+    module = Module.deserialize(store, Module(store, SUB_MODULE).serialize())
+    exports = Instance(module).exports
+
+    assert exports.sub(y=2, x=3) == 1
+
+def test_call_with_unknown_keyword_argument():
+    with pytest.raises(TypeError) as context_manager:
+        sub_instance().exports.sub(3, z=2)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function got an unexpected keyword argument `z`'
+
+def test_call_with_duplicated_keyword_argument():
+    with pytest.raises(TypeError) as context_manager:
+        sub_instance().exports.sub(3, x=2)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function got multiple values for argument `x`'
+
+def test_call_with_keyword_arguments_without_names():
+    with pytest.raises(TypeError) as context_manager:
+        sub_instance().exports.anonymous_sub(3, y=2)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function got an unexpected keyword argument `y`'
+
+def test_native():
+    sum = instance().exports.sum.native()
+
+    assert isinstance(sum, NativeFunction)
+    assert value_with_type(sum(1, 2)) == (3, int)
+
+def test_native_with_types():
+    sum = instance().exports.sum.native([Type.I32, Type.I32], [Type.I32])
+
+    assert sum(1, 2) == 3
+
+def test_native_various_signatures():
+    exports = instance().exports
+
+    assert value_with_type(exports.arity_0.native()()) == (42, int)
+    assert value_with_type(exports.i64_i64.native()(7)) == (7, int)
+    assert value_with_type(exports.f32_f32.native()(7.)) == (7., float)
+    assert value_with_type(exports.f64_f64.native()(7.)) == (7., float)
+    assert exports.void.native()() == None
+
+def test_native_with_mismatching_types():
+    with pytest.raises(TypeError):
+        instance().exports.sum.native([Type.I64, Type.I64], [Type.I64])
+
+def test_native_unsupported_signature():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.i32_i64_f32_f64_f64.native()
+
+    exception = context_manager.value
+    assert str(exception) == 'The signature `[I32, I64, F32, F64] -> [F64]` is not supported by native functions'
+
+def test_native_arity():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum.native()(1)
+
+    exception = context_manager.value
+    assert str(exception) == 'Function takes 2 argument(s) but 1 were given'
+
+def test_native_trap():
+    module = Module(Store(), '(module (func (export "crash") unreachable))')
+
+    with pytest.raises(wasmer.errors.Trap):
+        Instance(module).exports.crash.native()()
+
+def test_call_releases_the_gil():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory (export "memory") 1)
+          (func (export "wait") (result i32)
+            (local $countdown i32)
+            (local.set $countdown (i32.const 0x7fffffff))
+            (block $done
+              (loop $spin
+                (br_if $done (i32.load8_u (i32.const 0)))
+                (local.set $countdown (i32.sub (local.get $countdown) (i32.const 1)))
+                (br_if $spin (local.get $countdown))))
+            (i32.load8_u (i32.const 0))))
+        """
+    )
+    instance = Instance(module)
+    results = []
+
+    # `wait` spins until the first byte of the memory is set, or
+    # until it gives up. The byte can only be set in time if the GIL
+    # is released while the WebAssembly code runs, otherwise the main
+    # thread is blocked.
+    thread = threading.Thread(target=lambda: results.append(instance.exports.wait()), daemon=True)
+    thread.start()
+
+    time.sleep(0.1)
+    instance.exports.memory.uint8_view()[0] = 1
+
+    thread.join(timeout=5)
+    assert not thread.is_alive()
+    assert results == [1]
+
+def test_call_from_multiple_threads():
+    exports = instance().exports
+    results = [None] * 8
+
+    def run(index):
+        results[index] = exports.sum(index, 1)
+
+    threads = [threading.Thread(target=run, args=(index,)) for index in range(len(results))]
+
+    for thread in threads:
+        thread.start()
+
+    for thread in threads:
+        thread.join()
+
+    assert results == [index + 1 for index in range(len(results))]
+
+def test_host_function_from_another_thread():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+          (func (export "add_one") (param i32) (result i32)
+            local.get 0
+            i32.const 1
+            call $sum))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("math", { "sum": Function(store, sum) })
+
+    add_one = Instance(module, import_object).exports.add_one
+    results = []
+
+    thread = threading.Thread(target=lambda: results.append(add_one(41)))
+    thread.start()
+    thread.join()
+
+    assert results == [42]
+
+def test_call_async():
+    sum = instance().exports.sum
+
+    async def main():
+        return await sum.call_async(1, 2)
+
+    assert asyncio.get_event_loop().run_until_complete(main()) == 3
+
+def test_call_async_with_keyword_arguments():
+    sum = instance().exports.sum
+
+    async def main():
+        return await sum.call_async(1, y=2)
+
+    assert asyncio.get_event_loop().run_until_complete(main()) == 3
+
+def test_call_async_trap():
+    module = Module(Store(), '(module (func (export "crash") unreachable))')
+    crash = Instance(module).exports.crash
+
+    async def main():
+        await crash.call_async()
+
+    with pytest.raises(wasmer.errors.Trap):
+        asyncio.get_event_loop().run_until_complete(main())
+
+ASYNC_TEST_BYTES = """
+(module
+  (import "env" "fetch" (func $fetch (param i32) (result i32)))
+  (func (export "run") (param i32) (result i32)
+    local.get 0
+    call $fetch))
+"""
+
+def test_async_host_function_with_call_async():
+    event_loop = asyncio.get_event_loop()
+    loops = []
+
+    async def fetch(x: int) -> int:
+        loops.append(asyncio.get_event_loop())
+        await asyncio.sleep(0.01)
+
+        return x * 2
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "fetch": Function(store, fetch) })
+
+    run = Instance(Module(store, ASYNC_TEST_BYTES), import_object).exports.run
+
+    async def main():
+        return await asyncio.gather(run.call_async(1), run.call_async(2))
+
+    assert event_loop.run_until_complete(main()) == [2, 4]
+
+    # The coroutines have run on the event loop of the caller.
+    assert loops == [event_loop, event_loop]
+
+def test_async_host_function_with_call():
+    async def fetch(x: int) -> int:
+        await asyncio.sleep(0.01)
+
+        return x * 2
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "fetch": Function(store, fetch) })
+
+    assert Instance(Module(store, ASYNC_TEST_BYTES), import_object).exports.run(21) == 42
+
+def test_async_host_function_with_call_in_a_running_event_loop():
+    async def fetch(x: int) -> int:
+        return x * 2
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "fetch": Function(store, fetch) })
+
+    run = Instance(Module(store, ASYNC_TEST_BYTES), import_object).exports.run
+
+    async def main():
+        return run(21)
+
+    with pytest.raises(RuntimeError) as context_manager:
+        asyncio.get_event_loop().run_until_complete(main())
+
+    exception = context_manager.value
+    assert 'Function.call_async' in str(exception)
+
+def test_call_async_without_a_running_event_loop():
+    with pytest.raises(RuntimeError):
+        instance().exports.sum.call_async(1, 2)
+
+def test_async_host_function_exception():
+    async def fetch(x: int) -> int:
+        raise KeyError('oops')
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "fetch": Function(store, fetch) })
+
+    run = Instance(Module(store, ASYNC_TEST_BYTES), import_object).exports.run
+
+    async def main():
+        await run.call_async(1)
+
+    with pytest.raises(KeyError):
+        asyncio.get_event_loop().run_until_complete(main())
+
+RAW_BYTES = """
+(module
+  (func (export "f32_id") (param f32) (result f32) local.get 0)
+  (func (export "f64_id") (param f64) (result f64) local.get 0)
+  (func (export "pair") (param i32 f64) (result i32 f64) local.get 0 local.get 1))
+"""
+
+def test_call_raw():
+    exports = Instance(Module(Store(), RAW_BYTES)).exports
+
+    result = exports.f32_id.call((1.5,), raw=True)
+
+    assert isinstance(result, Value)
+    assert result == Value.f32(1.5)
+    assert result.value == 1.5
+
+def test_call_raw_keeps_nan_payloads():
+    exports = Instance(Module(Store(), RAW_BYTES)).exports
+
+    assert exports.f32_id.call((Value.f32_from_bits(0x7fc00001),), raw=True).bits == 0x7fc00001
+    assert exports.f64_id.call((Value.f64_from_bits(0x7ff8000000000001),), raw=True).bits == 0x7ff8000000000001
+
+def test_call_raw_multiple_results():
+    exports = Instance(Module(Store(), RAW_BYTES)).exports
+
+    assert exports.pair.call((1, 2.5), raw=True) == (Value.i32(1), Value.f64(2.5))
+    assert exports.pair.call((1, 2.5), raw=False) == (1, 2.5)
+
+def test_parameter_named_raw():
+    exports = Instance(
+        Module(
+            Store(),
+            '(module (func (export "id") (param $raw i32) (result i32) local.get $raw))'
+        )
+    ).exports
+
+    assert exports.id(raw=42) == 42
+    assert exports.id.call(keyword_arguments={'raw': 42}, raw=True) == Value.i32(42)
+
+RAW_HOST_BYTES = """
+(module
+  (import "env" "host" (func $host (param f32 i32) (result f32)))
+  (func (export "run") (param f32 i32) (result f32)
+    local.get 0
+    local.get 1
+    call $host))
+"""
+
+def test_raw_host_function():
+    received = []
+
+    def host(x: float, y: int) -> float:
+        received.append((x, y))
+
+        return x
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "host": Function(store, host, raw=True) })
+
+    exports = Instance(Module(store, RAW_HOST_BYTES), import_object).exports
+    result = exports.run.call((Value.f32_from_bits(0x7fc00001), 42), raw=True)
+
+    assert received == [(Value.f32_from_bits(0x7fc00001), Value.i32(42))]
+    assert result.bits == 0x7fc00001
+
+def test_host_function_is_not_raw_by_default():
+    received = []
+
+    def host(x: float, y: int) -> float:
+        received.append((x, y))
+
+        return x
+
+    store = Store()
+    import_object = ImportObject()
+    import_object.register("env", { "host": Function(store, host) })
+
+    exports = Instance(Module(store, RAW_HOST_BYTES), import_object).exports
+
+    assert exports.run(1.5, 42) == 1.5
+    assert received == [(1.5, 42)]
+
+def test_call_with_value_of_wrong_type():
+    exports = Instance(Module(Store(), RAW_BYTES)).exports
+
+    with pytest.raises(TypeError):
+        exports.f32_id(Value.f64(1.5))
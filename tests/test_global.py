@@ -1,5 +1,6 @@
 from wasmer import Instance, Module, Store, Global, GlobalType, Type, Value
 import pytest
+import threading
 
 TEST_BYTES = """
 (module
@@ -107,3 +108,34 @@ def test_global_read_write_constant():
     assert str(exception) == (
         'The global variable is not mutable, cannot set a new value'
     )
+
+def test_share_across_threads():
+    x = instance().exports.x
+
+    def run(value):
+        x.value = value
+
+    thread = threading.Thread(target=run, args=(153,))
+    thread.start()
+    thread.join()
+
+    assert x.value == 153
+
+def test_raw_value():
+    global_ = Global(Store(), Value.f32_from_bits(0x7fc00001), mutable=True)
+
+    assert global_.raw_value == Value.f32_from_bits(0x7fc00001)
+    assert global_.raw_value.bits == 0x7fc00001
+
+    global_.value = Value.f32_from_bits(0x7fc00002)
+
+    assert global_.raw_value.bits == 0x7fc00002
+
+def test_set_value_of_wrong_type():
+    global_ = Global(Store(), Value.i32(42), mutable=True)
+
+    with pytest.raises(TypeError) as context_manager:
+        global_.value = Value.i64(42)
+
+    exception = context_manager.value
+    assert str(exception) == 'The value is of type `Type.I64`, expected `Type.I32`'
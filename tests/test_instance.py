@@ -1,5 +1,6 @@
 import wasmer
 from wasmer import Instance, Module, Store, Exports, Function, Global, Table, Memory
+from concurrent.futures import ThreadPoolExecutor
 import os
 import pytest
 
@@ -59,3 +60,25 @@ def test_export_does_not_exist():
 
     exception = context_manager.value
     assert str(exception) == 'Export `foo` does not exist.'
+
+def test_share_across_threads():
+    instance = Instance(Module(Store(), TEST_BYTES))
+
+    def run(index):
+        return instance.exports.sum(index, 1)
+
+    with ThreadPoolExecutor(max_workers=4) as executor:
+        results = list(executor.map(run, range(16)))
+
+    assert results == [index + 1 for index in range(16)]
+
+def test_share_memory_across_threads():
+    memory = Instance(Module(Store(), TEST_BYTES)).exports.memory
+
+    def write(index):
+        memory.uint8_view()[index] = index
+
+    with ThreadPoolExecutor(max_workers=4) as executor:
+        list(executor.map(write, range(16)))
+
+    assert memory.uint8_view()[0:16] == list(range(16))
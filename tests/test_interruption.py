@@ -0,0 +1,152 @@
+import wasmer
+from wasmer import engine, Store, Module, Instance, InterruptHandle
+from wasmer_compiler_cranelift import Compiler
+import pytest
+import threading
+
+# The loop runs for much longer than the tests wait, but not forever:
+# if the interruption fails, the test fails instead of hanging.
+TEST_BYTES = """
+(module
+  (func (export "sum") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add)
+  (func (export "loop") (param $iterations i64)
+    (loop
+      local.get $iterations
+      i64.const 1
+      i64.sub
+      local.tee $iterations
+      i64.eqz
+      br_if 1
+      br 0))
+  (func (export "with_timeout") (param $timeout i32) (result i32)
+    local.get $timeout))
+"""
+
+LOOP_ITERATIONS = 10 ** 12
+
+def instance():
+    return Instance(Module(Store(engine.JIT(Compiler)), TEST_BYTES))
+
+def test_interrupt_handle():
+    assert isinstance(instance().interrupt_handle, InterruptHandle)
+
+def test_interrupt_handle_with_a_headless_engine():
+    serialized_module = Module(Store(engine.JIT(Compiler)), '(module)').serialize()
+    module = Module.deserialize(Store(engine.JIT()), serialized_module)
+
+    # The artifact has been compiled with the interruption checks.
+    assert isinstance(Instance(module).interrupt_handle, InterruptHandle)
+
+def test_interrupt():
+    instance_ = instance()
+    timer = threading.Timer(0.1, instance_.interrupt_handle.interrupt)
+    timer.daemon = True
+    timer.start()
+
+    with pytest.raises(wasmer.errors.Interrupted) as context_manager:
+        instance_.exports.loop(LOOP_ITERATIONS)
+
+    assert isinstance(context_manager.value, wasmer.errors.Trap)
+
+    # The instance can be used again.
+    assert instance_.exports.sum(1, 2) == 3
+
+def test_pending_interruption():
+    instance_ = instance()
+    instance_.interrupt_handle.interrupt()
+
+    with pytest.raises(wasmer.errors.Interrupted):
+        instance_.exports.sum(1, 2)
+
+    assert instance_.exports.sum(1, 2) == 3
+
+def test_timeout():
+    instance_ = instance()
+
+    with pytest.raises(wasmer.errors.Interrupted):
+        instance_.exports.loop.call((LOOP_ITERATIONS,), timeout=0.1)
+
+    assert instance_.exports.sum.call((1, 2), timeout=1) == 3
+
+def test_timeout_is_not_reached():
+    assert instance().exports.sum.call((1, 2), timeout=5) == 3
+
+def test_timeout_does_not_leave_an_interruption_pending():
+    instance_ = instance()
+
+    # The timeout expires while or right after the call runs.
+    for _ in range(100):
+        try:
+            instance_.exports.sum.call((1, 2), timeout=0)
+        except wasmer.errors.Interrupted:
+            pass
+
+        assert instance_.exports.sum(1, 2) == 3
+
+def test_timeouts_from_multiple_threads():
+    instances = [instance() for _ in range(4)]
+    errors = [None] * len(instances)
+
+    def run(index):
+        try:
+            instances[index].exports.loop.call((LOOP_ITERATIONS,), timeout=0.1 * (index + 1))
+        except Exception as error:
+            errors[index] = error
+
+    threads = [threading.Thread(target=run, args=(index,), daemon=True) for index in range(len(instances))]
+
+    for thread in threads:
+        thread.start()
+
+    for thread in threads:
+        thread.join(timeout=30)
+
+    assert all(isinstance(error, wasmer.errors.Interrupted) for error in errors)
+
+def test_timeout_does_not_interrupt_other_calls():
+    instance_ = instance()
+    errors = []
+
+    def run():
+        try:
+            instance_.exports.loop(10 ** 8)
+        except Exception as error:
+            errors.append(error)
+
+    thread = threading.Thread(target=run, daemon=True)
+    thread.start()
+
+    # Either the timed call waits for the other call and expires
+    # before it could start, or it runs alone and the other call
+    # waits for it: the other call is never interrupted.
+    with pytest.raises(wasmer.errors.Interrupted):
+        instance_.exports.loop.call((LOOP_ITERATIONS,), timeout=0.01)
+
+    thread.join(timeout=30)
+
+    assert errors == []
+
+def test_timeout_with_a_parameter_named_timeout():
+    function = instance().exports.with_timeout
+
+    assert function(timeout=7) == 7
+    assert function.call(keyword_arguments={'timeout': 7}, timeout=1) == 7
+
+def test_invalid_timeout():
+    with pytest.raises(ValueError) as context_manager:
+        instance().exports.sum.call((1, 2), timeout=-1)
+
+    exception = context_manager.value
+    assert str(exception) == 'The timeout must be a positive number of seconds'
+
+def test_timeout_without_instance():
+    function = wasmer.Function(Store(), lambda: None)
+
+    with pytest.raises(RuntimeError) as context_manager:
+        function.call(timeout=1)
+
+    exception = context_manager.value
+    assert str(exception) == 'The function cannot be interrupted; it must be exported by an instance compiled with a compiler'
@@ -1,7 +1,8 @@
-from wasmer import Instance, Module, Store, Memory, MemoryType, Buffer, Uint8Array, Int8Array, Uint16Array, Int16Array, Uint32Array, Int32Array
+from wasmer import Instance, Module, Store, Memory, MemoryType, Buffer, Uint8Array, Int8Array, Uint16Array, Int16Array, Uint32Array, Int32Array, Uint64Array, Int64Array, Float32Array, Float64Array
 import inspect
 import os
 import pytest
+import struct
 
 here = os.path.dirname(os.path.realpath(__file__))
 TEST_BYTES = open(here + '/tests.wasm', 'rb').read()
@@ -45,6 +46,10 @@ def test_is_a_class():
     assert inspect.isclass(Int16Array)
     assert inspect.isclass(Uint32Array)
     assert inspect.isclass(Int32Array)
+    assert inspect.isclass(Uint64Array)
+    assert inspect.isclass(Int64Array)
+    assert inspect.isclass(Float32Array)
+    assert inspect.isclass(Float64Array)
     assert inspect.isclass(Buffer)
 
 def test_bytes_per_element():
@@ -56,6 +61,10 @@ def test_bytes_per_element():
     assert memory.int16_view().bytes_per_element ==  2
     assert memory.uint32_view().bytes_per_element ==  4
     assert memory.int32_view().bytes_per_element ==  4
+    assert memory.uint64_view().bytes_per_element ==  8
+    assert memory.int64_view().bytes_per_element ==  8
+    assert memory.float32_view().bytes_per_element ==  4
+    assert memory.float64_view().bytes_per_element ==  8
 
 @pytest.mark.xfail()
 def test_cannot_construct():
@@ -212,6 +221,61 @@ def test_memory_views_share_the_same_buffer():
     assert byte_array[2] == 0b00010000
     assert byte_array[3] == 0b01000000
 
+def test_memory_64_bits_views_share_the_same_buffer():
+    memory = instance().exports.memory
+    uint8 = memory.uint8_view()
+    uint64 = memory.uint64_view()
+    int64 = memory.int64_view()
+
+    uint8[0:8] = [1, 2, 3, 4, 5, 6, 7, 0x80]
+
+    assert uint64[0] == 0x8007060504030201
+    assert int64[0] == 0x8007060504030201 - (1 << 64)
+
+    int64[1] = -1
+
+    assert uint8[8:16] == [0xff] * 8
+    assert uint64[1] == (1 << 64) - 1
+
+def test_memory_float_views():
+    memory = instance().exports.memory
+    float32 = memory.float32_view()
+    float64 = memory.float64_view(offset=2)
+
+    float32[0] = 1.5
+    float32[1] = 3
+
+    assert float32[0] == 1.5
+    assert float32[1] == 3.0
+    assert bytes(memory.buffer)[0:8] == struct.pack('<ff', 1.5, 3.0)
+
+    float64[0:3] = [0.5, -2.25, 1e300]
+
+    assert float64[0:3] == [0.5, -2.25, 1e300]
+    assert bytes(memory.buffer)[16:40] == struct.pack('<ddd', 0.5, -2.25, 1e300)
+
+def test_memory_integer_views_reject_floats():
+    int64 = instance().exports.memory.int64_view()
+
+    with pytest.raises(RuntimeError) as context_manager:
+        int64[0] = 1.0
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'When setting data to the memory view, the index and the value can only have the following types: Either `int` and `int`, or `slice` and `sequence`'
+    )
+
+def test_memory_float_views_reject_invalid_values():
+    float32 = instance().exports.memory.float32_view()
+
+    with pytest.raises(RuntimeError) as context_manager:
+        float32[0] = 'foo'
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'When setting data to the memory view, the index and the value can only have the following types: Either `int` and `float`, or `slice` and `sequence`'
+    )
+
 def test_memory_grow():
     memory = instance().exports.memory
     int8 = memory.int8_view()
@@ -0,0 +1,192 @@
+import wasmer
+from wasmer import engine, Store, Module, Instance
+from wasmer_compiler_cranelift import Compiler
+import pytest
+import threading
+
+TEST_BYTES = """
+(module
+  (func (export "sum") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    i32.add)
+  (func (export "loop")
+    (loop
+      br 0)))
+"""
+
+def instance(initial_points, costs=None):
+    metering = engine.Metering(initial_points, costs)
+    store = Store(engine.JIT(Compiler, metering=metering))
+
+    return Instance(Module(store, TEST_BYTES))
+
+def test_metering():
+    metering = engine.Metering(initial_points=100, costs={'I32Add': 10})
+
+    assert metering.initial_points == 100
+    assert metering.costs == {'I32Add': 10}
+
+def test_metering_requires_a_compiler():
+    with pytest.raises(RuntimeError) as context_manager:
+        engine.JIT(metering=engine.Metering(100))
+
+    exception = context_manager.value
+    assert str(exception) == 'Metering requires a compiler'
+
+def test_remaining_points():
+    instance_ = instance(100)
+
+    assert instance_.remaining_points == 100
+    assert instance_.exports.sum(1, 2) == 3
+    assert instance_.remaining_points < 100
+
+def test_costs():
+    cheap = instance(100)
+    expensive = instance(100, {'I32Add': 10})
+
+    cheap.exports.sum(1, 2)
+    expensive.exports.sum(1, 2)
+
+    assert 100 - expensive.remaining_points == 100 - cheap.remaining_points + 9
+
+def test_set_remaining_points():
+    instance_ = instance(100)
+    instance_.set_remaining_points(42)
+
+    assert instance_.remaining_points == 42
+
+def test_exhaustion():
+    instance_ = instance(1000)
+
+    with pytest.raises(wasmer.errors.MeteringPointsExhausted) as context_manager:
+        instance_.exports.loop()
+
+    assert isinstance(context_manager.value, wasmer.errors.Trap)
+    assert instance_.remaining_points == 0
+
+    # Points can be refilled to resume the execution.
+    instance_.set_remaining_points(100)
+
+    assert instance_.exports.sum(1, 2) == 3
+
+def test_exhaustion_with_native_function():
+    instance_ = instance(1000)
+
+    with pytest.raises(wasmer.errors.MeteringPointsExhausted):
+        instance_.exports.loop.native()()
+
+def test_multiple_modules_with_one_store():
+    store = Store(engine.JIT(Compiler, metering=engine.Metering(1000)))
+
+    # The modules have a different number of globals, so the globals
+    # injected by the metering have different indices.
+    first = Module(store, TEST_BYTES)
+    second = Module(
+        store,
+        """
+        (module
+          (global $x (mut i32) (i32.const 40))
+          (global $y (mut i32) (i32.const 2))
+          (func (export "answer") (result i32)
+            global.get $x
+            global.get $y
+            i32.add))
+        """
+    )
+
+    first_instance = Instance(first)
+    second_instance = Instance(second)
+
+    assert first_instance.exports.sum(1, 2) == 3
+    assert second_instance.exports.answer() == 42
+    assert first_instance.remaining_points < 1000
+    assert second_instance.remaining_points < 1000
+
+    with pytest.raises(wasmer.errors.MeteringPointsExhausted):
+        first_instance.exports.loop()
+
+    # The exhaustion of an instance does not impact the others.
+    assert second_instance.exports.answer() == 42
+
+def test_exhaustion_through_a_table():
+    store = Store(engine.JIT(Compiler, metering=engine.Metering(1000)))
+    instance_ = Instance(
+        Module(
+            store,
+            """
+            (module
+              (table (export "table") 1 funcref)
+              (elem (i32.const 0) $loop)
+              (func $loop
+                (loop
+                  br 0)))
+            """
+        )
+    )
+
+    # The function read from the table is attributed to the instance.
+    with pytest.raises(wasmer.errors.MeteringPointsExhausted):
+        instance_.exports.table[0]()
+
+    assert instance_.remaining_points == 0
+
+def test_not_metered():
+    instance_ = Instance(Module(Store(), TEST_BYTES))
+
+    with pytest.raises(RuntimeError) as context_manager:
+        instance_.remaining_points
+
+    exception = context_manager.value
+    assert str(exception) == 'The module has not been compiled with metering; see `engine.Metering`'
+
+# Enough points to loop for much longer than the tests wait, but not
+# forever: if the interruption fails, the points are exhausted and
+# the test fails instead of hanging.
+LOOP_POINTS = 10 ** 10
+
+def test_interrupt():
+    instance_ = instance(LOOP_POINTS)
+    timer = threading.Timer(0.1, instance_.interrupt_handle.interrupt)
+    timer.daemon = True
+    timer.start()
+
+    with pytest.raises(wasmer.errors.Interrupted) as context_manager:
+        instance_.exports.loop()
+
+    assert not isinstance(context_manager.value, wasmer.errors.MeteringPointsExhausted)
+
+    # The interruption does not consume the points, the instance can
+    # be used again.
+    remaining_points = instance_.remaining_points
+
+    assert 0 < remaining_points < LOOP_POINTS
+    assert instance_.exports.sum(1, 2) == 3
+    assert instance_.remaining_points < remaining_points
+
+def test_pending_interruption():
+    instance_ = instance(100)
+    instance_.interrupt_handle.interrupt()
+
+    with pytest.raises(wasmer.errors.Interrupted):
+        instance_.exports.sum(1, 2)
+
+    assert instance_.remaining_points == 100
+
+def test_timeout():
+    instance_ = instance(LOOP_POINTS)
+
+    with pytest.raises(wasmer.errors.Interrupted):
+        instance_.exports.loop.call(timeout=0.1)
+
+    assert instance_.remaining_points > 0
+
+def test_unmetered_artifact_in_a_metered_store():
+    serialized_module = Module(Store(engine.JIT(Compiler)), TEST_BYTES).serialize()
+    store = Store(engine.JIT(Compiler, metering=engine.Metering(100)))
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(store, serialized_module)
+
+    exception = context_manager.value
+    assert str(exception) == 'The module has been serialized for the metering `disabled`, but the store has `enabled`'
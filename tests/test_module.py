@@ -1,5 +1,6 @@
 import wasmer
-from wasmer import Store, Module, ExportType, ImportType, FunctionType, MemoryType, GlobalType, TableType, Type
+from wasmer import Store, Module, Instance, ExportType, ImportType, FunctionType, MemoryType, GlobalType, TableType, Type
+from concurrent.futures import ThreadPoolExecutor
 from enum import IntEnum
 import os
 import pytest
@@ -14,12 +15,116 @@ def test_validate():
 def test_validate_invalid():
     assert not Module.validate(Store(), INVALID_TEST_BYTES)
 
+def test_validate_wat():
+    assert Module.validate(Store(), '(module)')
+
+def test_validate_memoryview():
+    assert Module.validate(Store(), memoryview(TEST_BYTES))
+
+def test_validate_unsupported_input():
+    with pytest.raises(TypeError):
+        Module.validate(Store(), 42)
+
+def test_validate_raise_error():
+    assert Module.validate(Store(), TEST_BYTES, raise_error=True)
+
+    with pytest.raises(wasmer.errors.ValidationError) as context_manager:
+        Module.validate(Store(), INVALID_TEST_BYTES, raise_error=True)
+
+    exception = context_manager.value
+    assert isinstance(exception, wasmer.errors.CompileError)
+    assert isinstance(exception.reason, str)
+    assert isinstance(exception.offset, int)
+    assert str(exception).startswith(exception.reason)
+
+def test_validate_invalid_wat():
+    with pytest.raises(wasmer.errors.ValidationError) as context_manager:
+        Module.validate(Store(), '(module', raise_error=True)
+
+    exception = context_manager.value
+    assert exception.offset == None
+    assert exception.section == None
+
+def test_validate_features():
+    from wasmer import engine
+
+    wat = '(module (func (result i32 i32) i32.const 1 i32.const 2))'
+
+    assert Module.validate(Store(), wat)
+    assert not Module.validate(Store(), wat, features=engine.Features(multi_value=False))
+
+    with pytest.raises(wasmer.errors.ValidationError) as context_manager:
+        Module.validate(Store(), wat, features=engine.Features(multi_value=False), raise_error=True)
+
+    exception = context_manager.value
+    assert exception.section == 'type'
+    assert str(exception) == '{} (at offset {}, in the type section)'.format(exception.reason, exception.offset)
+
+def test_validate_simd():
+    from wasmer import engine
+
+    wat = '(module (func (result v128) v128.const i32x4 1 2 3 4))'
+
+    assert not Module.validate(Store(), wat)
+    assert Module.validate(Store(), wat, features=engine.Features(simd=True))
+
 def test_compile_bytes():
     assert isinstance(Module(Store(), TEST_BYTES), Module)
 
 def test_compile_wat():
     assert isinstance(Module(Store(), '(module)'), Module)
 
+def test_compile_path():
+    from pathlib import Path
+
+    module = Module(Store(), Path(here) / 'tests.wasm')
+
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_compile_missing_path(tmp_path):
+    with pytest.raises(FileNotFoundError):
+        Module(Store(), tmp_path / 'missing.wasm')
+
+def test_compile_bytearray():
+    assert Instance(Module(Store(), bytearray(TEST_BYTES))).exports.sum(1, 2) == 3
+
+def test_compile_memoryview():
+    assert Instance(Module(Store(), memoryview(TEST_BYTES))).exports.sum(1, 2) == 3
+
+def test_compile_non_contiguous_memoryview():
+    with pytest.raises(TypeError) as context_manager:
+        Module(Store(), memoryview(TEST_BYTES)[::2])
+
+    exception = context_manager.value
+    assert str(exception) == '`Module` accepts contiguous buffers only'
+
+def test_compile_binary_file():
+    with open(here + '/tests.wasm', 'rb') as file:
+        module = Module(Store(), file)
+
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_compile_text_file(tmp_path):
+    path = tmp_path / 'module.wat'
+    path.write_text('(module (func (export "answer") (result i32) i32.const 42))')
+
+    with open(path) as file:
+        module = Module(Store(), file)
+
+    assert Instance(module).exports.answer() == 42
+
+def test_compile_file_like():
+    import io
+
+    assert Instance(Module(Store(), io.BytesIO(TEST_BYTES))).exports.sum(1, 2) == 3
+
+def test_compile_unsupported_input():
+    with pytest.raises(TypeError) as context_manager:
+        Module(Store(), 42)
+
+    exception = context_manager.value
+    assert str(exception) == '`Module` accepts Wasm bytes, a WAT string, a path, a buffer or a file-like object'
+
 def test_failed_to_compile():
     with pytest.raises(RuntimeError) as context_manager:
         Module(Store(), INVALID_TEST_BYTES)
@@ -140,3 +245,152 @@ def test_deserialize():
     assert isinstance(exports[0].type, FunctionType)
     assert exports[0].type.params == [Type.I32, Type.I64]
     assert exports[0].type.results == []
+
+def test_serialize_envelope():
+    serialized_module = Module(Store(), "(module)").serialize()
+
+    assert serialized_module.startswith(b'\0wasmer-python\0')
+
+def test_deserialize_not_a_serialized_module():
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(Store(), b'foobar')
+
+    exception = context_manager.value
+    assert isinstance(exception, RuntimeError)
+    assert str(exception) == 'The bytes are not a serialized module'
+
+def test_deserialize_corrupted():
+    store = Store()
+    serialized_module = bytearray(Module(store, TEST_BYTES).serialize())
+    serialized_module[-1] ^= 0xff
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(store, bytes(serialized_module))
+
+    exception = context_manager.value
+    assert str(exception) == 'The serialized module is corrupted'
+
+def test_deserialize_truncated():
+    store = Store()
+    serialized_module = Module(store, TEST_BYTES).serialize()
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(store, serialized_module[:len(serialized_module) // 2])
+
+    exception = context_manager.value
+    assert str(exception) == 'The serialized module is corrupted'
+
+def test_deserialize_unsupported_format_version():
+    store = Store()
+    serialized_module = bytearray(Module(store, TEST_BYTES).serialize())
+    serialized_module[15] = 42
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(store, bytes(serialized_module))
+
+    exception = context_manager.value
+    assert str(exception) == 'The serialization format version 42 is not supported, expected 1'
+
+def test_deserialize_another_engine():
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    serialized_module = Module(Store(engine.JIT(Compiler)), TEST_BYTES).serialize()
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(Store(engine.Native(Compiler)), serialized_module)
+
+    exception = context_manager.value
+    assert str(exception) == 'The module has been serialized for the engine `jit`, but the store has `native`'
+
+def test_deserialize_another_metering():
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    metering = engine.Metering(100)
+    serialized_module = Module(Store(engine.JIT(Compiler, metering=metering)), TEST_BYTES).serialize()
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(Store(engine.JIT(Compiler)), serialized_module)
+
+    exception = context_manager.value
+    assert str(exception) == 'The module has been serialized for the metering `enabled`, but the store has `disabled`'
+
+def test_deserialize_other_features():
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    features = engine.Features(simd=True)
+    serialized_module = Module(Store(engine.JIT(Compiler, features=features)), TEST_BYTES).serialize()
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize(Store(engine.JIT(Compiler)), serialized_module)
+
+    exception = context_manager.value
+    assert str(exception).startswith('The module has been serialized for the features `')
+
+def test_deserialize_headless():
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    serialized_module = Module(Store(engine.JIT(Compiler)), TEST_BYTES).serialize()
+    module = Module.deserialize(Store(engine.JIT()), serialized_module)
+
+    assert Instance(module).exports.sum(1, 2) == 3
+
+@pytest.mark.parametrize('engine_name', ['JIT', 'Native'])
+def test_deserialize_from_file(tmp_path, engine_name):
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    store = Store(getattr(engine, engine_name)(Compiler))
+    path = tmp_path / 'module.wasmer'
+    path.write_bytes(Module(store, TEST_BYTES).serialize())
+
+    module = Module.deserialize_from_file(store, path)
+    assert Instance(module).exports.sum(1, 2) == 3
+
+    module = Module.deserialize_from_file(store, str(path))
+    assert Instance(module).exports.sum(1, 2) == 3
+
+    module = Module.deserialize_from_file(store, bytes(path))
+    assert Instance(module).exports.sum(1, 2) == 3
+
+def test_deserialize_from_file_corrupted(tmp_path):
+    store = Store()
+    serialized_module = bytearray(Module(store, TEST_BYTES).serialize())
+    serialized_module[-1] ^= 0xff
+
+    path = tmp_path / 'module.wasmer'
+    path.write_bytes(bytes(serialized_module))
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize_from_file(store, path)
+
+    exception = context_manager.value
+    assert str(exception) == 'The serialized module is corrupted'
+
+def test_deserialize_from_empty_file(tmp_path):
+    path = tmp_path / 'module.wasmer'
+    path.write_bytes(b'')
+
+    with pytest.raises(wasmer.errors.DeserializeError) as context_manager:
+        Module.deserialize_from_file(Store(), path)
+
+    exception = context_manager.value
+    assert str(exception) == 'The bytes are not a serialized module'
+
+def test_deserialize_from_missing_file(tmp_path):
+    with pytest.raises(FileNotFoundError):
+        Module.deserialize_from_file(Store(), tmp_path / 'missing.wasmer')
+
+def test_share_across_threads():
+    module = Module(Store(), TEST_BYTES)
+
+    def run(index):
+        return Instance(module).exports.sum(index, 1)
+
+    with ThreadPoolExecutor(max_workers=4) as executor:
+        results = list(executor.map(run, range(16)))
+
+    assert results == [index + 1 for index in range(16)]
@@ -0,0 +1,137 @@
+import wasmer
+from wasmer import engine, Store, Module, Instance, Function, FunctionType, Global, ImportObject, Table, TableType, Type, Value
+from wasmer_compiler_cranelift import Compiler
+import gc
+import weakref
+
+def store():
+    return Store(engine.JIT(Compiler, features=engine.Features(reference_types=True)))
+
+class Object:
+    pass
+
+def test_marker_types():
+    def function(a: wasmer.externref, b: wasmer.funcref) -> wasmer.externref:
+        return a
+
+    type = Function(store(), function).type
+
+    assert type.params == [Type.EXTERN_REF, Type.FUNC_REF]
+    assert type.results == [Type.EXTERN_REF]
+
+def test_function_type():
+    function = Function(
+        store(),
+        lambda a: a,
+        FunctionType([Type.EXTERN_REF], [Type.EXTERN_REF]),
+    )
+
+    assert function.type.params == [Type.EXTERN_REF]
+
+def test_externref_round_trip():
+    instance = Instance(
+        Module(
+            store(),
+            """
+            (module
+              (func (export "identity") (param externref) (result externref)
+                local.get 0))
+            """
+        )
+    )
+    identity = instance.exports.identity
+    object = Object()
+
+    assert identity(object) is object
+    assert identity({'a': 1}) == {'a': 1}
+    assert identity(None) is None
+
+def test_externref_through_host_function():
+    store_ = store()
+    received = []
+
+    def host(value: wasmer.externref) -> wasmer.externref:
+        received.append(value)
+
+        return value
+
+    import_object = ImportObject()
+    import_object.register('env', {'host': Function(store_, host)})
+
+    instance = Instance(
+        Module(
+            store_,
+            """
+            (module
+              (import "env" "host" (func $host (param externref) (result externref)))
+              (func (export "call_host") (param externref) (result externref)
+                local.get 0
+                call $host))
+            """
+        ),
+        import_object,
+    )
+    object = Object()
+
+    assert instance.exports.call_host(object) is object
+    assert received == [object]
+
+def test_funcref_round_trip():
+    store_ = store()
+    instance = Instance(
+        Module(
+            store_,
+            """
+            (module
+              (func (export "identity") (param funcref) (result funcref)
+                local.get 0))
+            """
+        )
+    )
+
+    def answer() -> int:
+        return 42
+
+    function = instance.exports.identity(Function(store_, answer))
+
+    assert isinstance(function, Function)
+    assert function() == 42
+    assert instance.exports.identity(None) is None
+
+def test_externref_global():
+    object = Object()
+    global_ = Global(store(), Value.externref(object), mutable=True)
+
+    assert global_.type.type == Type.EXTERN_REF
+    assert global_.value is object
+
+    global_.value = None
+
+    assert global_.value is None
+
+def test_externref_table():
+    objects = [Object(), Object()]
+    table = Table(store(), TableType(Type.EXTERN_REF, minimum=2))
+
+    assert list(table) == [None, None]
+
+    table[0] = objects[0]
+    table.set(1, objects[1])
+
+    assert list(table) == objects
+
+def test_externref_keeps_the_object_alive():
+    object = Object()
+    reference = weakref.ref(object)
+    global_ = Global(store(), Value.externref(object), mutable=True)
+
+    del object
+    gc.collect()
+
+    assert reference() is not None
+    assert global_.value is reference()
+
+    global_.value = None
+    gc.collect()
+
+    assert reference() is None
@@ -1,4 +1,4 @@
-from wasmer import engine, Store, Module, Instance
+from wasmer import engine, errors, Store, Module, Instance, Memory, MemoryType, Limits
 import wasmer_compiler_cranelift
 #import wasmer_compiler_llvm
 import wasmer_compiler_singlepass
@@ -50,3 +50,135 @@ def test_store_with_various_engines_and_compilers():
             instance = Instance(module)
 
             assert instance.exports.sum(1, 2)
+
+def test_limits():
+    limits = Limits(memory_pages=16, table_elements=32, instances=2)
+
+    assert limits.memory_pages == 16
+    assert limits.table_elements == 32
+    assert limits.instances == 2
+    assert limits.static_memory_bound == None
+    assert limits.static_memory_offset_guard_size == None
+    assert limits.dynamic_memory_offset_guard_size == None
+
+def test_limits_memory_grow():
+    store = Store(limits=Limits(memory_pages=16))
+    memory = Memory(store, MemoryType(1, shared=False))
+
+    assert memory.grow(15) == 1
+
+    with pytest.raises(errors.MemoryError) as context_manager:
+        memory.grow(1)
+
+    exception = context_manager.value
+    assert exception.current == 16
+    assert exception.attempted_delta == 1
+
+def test_limits_memory_minimum():
+    store = Store(limits=Limits(memory_pages=16))
+
+    with pytest.raises(errors.MemoryError) as context_manager:
+        Memory(store, MemoryType(17, shared=False))
+
+    exception = context_manager.value
+    assert str(exception) == 'The memory minimum of 17 pages exceeds the limit of 16 pages'
+
+def test_limits_memory_instantiation():
+    store = Store(limits=Limits(memory_pages=16))
+    module = Module(store, '(module (memory 17))')
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module)
+
+def test_limits_memory_grow_from_webassembly():
+    store = Store(limits=Limits(memory_pages=16))
+    module = Module(
+        store,
+        """
+        (module
+          (memory 1)
+          (func (export "grow") (param i32) (result i32)
+            local.get 0
+            memory.grow))
+        """
+    )
+    grow = Instance(module).exports.grow
+
+    assert grow(15) == 1
+    assert grow(1) == -1
+
+def test_limits_table():
+    store = Store(limits=Limits(table_elements=32))
+    module = Module(store, '(module (table 64 funcref))')
+
+    with pytest.raises(errors.InstantiationError) as context_manager:
+        Instance(module)
+
+    exception = context_manager.value
+    assert 'The table minimum of 64 elements exceeds the limit of 32 elements' in str(exception)
+
+def test_limits_instances():
+    store = Store(limits=Limits(instances=2))
+    module = Module(store, '(module)')
+
+    first = Instance(module)
+    second = Instance(module)
+
+    with pytest.raises(errors.InstantiationError) as context_manager:
+        Instance(module)
+
+    exception = context_manager.value
+    assert str(exception) == 'The number of instances exceeds the limit of 2'
+
+    # Dropping an instance frees a slot.
+    del first
+
+    assert isinstance(Instance(module), Instance)
+
+def test_limits_instances_with_exported_function():
+    store = Store(limits=Limits(instances=1))
+    module = Module(store, '(module (func (export "function")))')
+
+    # The function keeps the instance alive, and thus counted.
+    function = Instance(module).exports.function
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module)
+
+    del function
+
+    assert isinstance(Instance(module), Instance)
+
+def test_limits_instances_with_exported_memory():
+    store = Store(limits=Limits(instances=1))
+    module = Module(store, '(module (memory (export "memory") 1))')
+
+    # The memory, and any view over it, keeps the instance alive,
+    # and thus counted.
+    memory = Instance(module).exports.memory
+    view = memory.uint8_view()
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module)
+
+    del memory
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module)
+
+    del view
+
+    assert isinstance(Instance(module), Instance)
+
+def test_limits_instances_with_exported_table():
+    store = Store(limits=Limits(instances=1))
+    module = Module(store, '(module (table (export "table") 1 funcref))')
+
+    table = Instance(module).exports.table
+
+    with pytest.raises(errors.InstantiationError):
+        Instance(module)
+
+    del table
+
+    assert isinstance(Instance(module), Instance)
@@ -1,4 +1,4 @@
-from wasmer import Instance, Module, Store, Table, TableType, Type
+from wasmer import Function, Instance, Module, Store, Table, TableType, Type
 import pytest
 
 TEST_BYTES = """
@@ -21,3 +21,137 @@ def test_type():
 
 def test_size():
     assert instance().exports.table.size == 0
+
+ELEMENTS_BYTES = """
+(module
+  (table (export "table") 2 4 funcref)
+  (func $one (export "one") (result i32) i32.const 1)
+  (func $two (export "two") (result i32) i32.const 2)
+  (elem (i32.const 0) $one $two)
+  (func (export "call_indirect") (param i32) (result i32)
+    local.get 0
+    call_indirect (result i32)))
+"""
+
+def elements_instance():
+    return Instance(Module(Store(), ELEMENTS_BYTES))
+
+def test_get():
+    table = elements_instance().exports.table
+
+    assert isinstance(table.get(0), Function)
+    assert table.get(0)() == 1
+    assert table.get(1)() == 2
+
+def test_get_out_of_bound():
+    table = elements_instance().exports.table
+
+    with pytest.raises(IndexError) as context_manager:
+        table.get(2)
+
+    exception = context_manager.value
+    assert str(exception) == 'Out of bound: Index 2 is larger than the table size 2'
+
+def test_set():
+    exports = elements_instance().exports
+    table = exports.table
+
+    table.set(0, exports.two)
+
+    assert table.get(0)() == 2
+    assert exports.call_indirect(0) == 2
+
+def test_set_none():
+    exports = elements_instance().exports
+    table = exports.table
+
+    table.set(0, None)
+
+    assert table.get(0) == None
+
+    with pytest.raises(RuntimeError):
+        exports.call_indirect(0)
+
+def test_set_host_function():
+    store = Store()
+    instance = Instance(Module(store, ELEMENTS_BYTES))
+
+    def three() -> int:
+        return 3
+
+    instance.exports.table.set(1, Function(store, three))
+
+    assert instance.exports.call_indirect(1) == 3
+
+def test_set_wrong_type():
+    table = elements_instance().exports.table
+
+    with pytest.raises(TypeError):
+        table.set(0, 42)
+
+def test_grow():
+    exports = elements_instance().exports
+    table = exports.table
+
+    assert table.grow(1) == 2
+    assert table.size == 3
+    assert table.get(2) == None
+
+    assert table.grow(1, exports.one) == 3
+    assert table.get(3)() == 1
+
+def test_grow_beyond_maximum():
+    table = elements_instance().exports.table
+
+    with pytest.raises(RuntimeError):
+        table.grow(3)
+
+    assert table.size == 2
+
+def test_copy():
+    exports = elements_instance().exports
+    table = exports.table
+
+    table.copy(0, 1, 1)
+
+    assert table.get(0)() == 2
+    assert table.get(1)() == 2
+
+def test_copy_from_another_table():
+    store = Store()
+    exports = Instance(Module(store, ELEMENTS_BYTES)).exports
+    table = Table(store, TableType(Type.FUNC_REF, minimum=3))
+
+    table.copy(1, 0, 2, source=exports.table)
+
+    assert table.get(0) == None
+    assert table.get(1)() == 1
+    assert table.get(2)() == 2
+
+def test_copy_out_of_bound():
+    table = elements_instance().exports.table
+
+    with pytest.raises(RuntimeError):
+        table.copy(1, 0, 2)
+
+def test_host_table():
+    store = Store()
+    table = Table(store, TableType(Type.FUNC_REF, minimum=2, maximum=None))
+
+    assert table.size == 2
+    assert table.get(0) == None
+
+def test_sequence_protocol():
+    exports = elements_instance().exports
+    table = exports.table
+
+    assert len(table) == 2
+    assert table[0]() == 1
+    assert [function() for function in table] == [1, 2]
+
+    table[0] = exports.two
+
+    assert table[0]() == 2
+
+    with pytest.raises(IndexError):
+        table[2]
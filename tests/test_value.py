@@ -1,6 +1,9 @@
-from wasmer import Value
+import wasmer
+from wasmer import engine, Function, Global, ImportObject, Instance, Module, Store, Type, Value
+from wasmer_compiler_cranelift import Compiler
 import inspect
 import pytest
+import struct
 
 def test_is_a_class():
     assert inspect.isclass(Value)
@@ -29,3 +32,206 @@ def test_f64_auto_cast():
 
 def test_v128():
     assert repr(Value.v128(340282366920938463463374607431768211455)) == 'V128(340282366920938463463374607431768211455)'
+
+def test_funcref():
+    def answer() -> int:
+        return 42
+
+    assert repr(Value.funcref(None)).startswith('FuncRef')
+    assert repr(Value.funcref(Function(Store(), answer))).startswith('FuncRef')
+
+def test_funcref_wrong_type():
+    with pytest.raises(TypeError):
+        Value.funcref(42)
+
+def test_externref():
+    assert repr(Value.externref({'a': 1})).startswith('ExternRef')
+    assert repr(Value.externref(None)).startswith('ExternRef')
+
+V128 = 0x0f0e0d0c0b0a09080706050403020100
+V128_BYTES = bytes(range(16))
+
+def test_v128_from_bytes():
+    assert repr(Value.v128(V128_BYTES)) == 'V128({})'.format(V128)
+
+def test_v128_from_buffer():
+    assert repr(Value.v128(bytearray(V128_BYTES))) == 'V128({})'.format(V128)
+    assert repr(Value.v128(memoryview(V128_BYTES))) == 'V128({})'.format(V128)
+
+def test_v128_from_wrong_number_of_bytes():
+    with pytest.raises(ValueError) as context_manager:
+        Value.v128(b'\x00' * 15)
+
+    exception = context_manager.value
+    assert str(exception) == 'A `v128` value must have 16 bytes, given 15'
+
+def test_v128_from_negative_int():
+    assert repr(Value.v128(-1)) == 'V128(340282366920938463463374607431768211455)'
+
+@pytest.mark.parametrize('lanes, shape', [
+    (tuple(range(16)), 'i8x16'),
+    ((0x0100, 0x0302, 0x0504, 0x0706, 0x0908, 0x0b0a, 0x0d0c, 0x0f0e), 'i16x8'),
+    ((0x03020100, 0x07060504, 0x0b0a0908, 0x0f0e0d0c), 'i32x4'),
+    ((0x0706050403020100, 0x0f0e0d0c0b0a0908), 'i64x2'),
+])
+def test_v128_from_integer_lanes(lanes, shape):
+    assert repr(Value.v128(lanes, shape=shape)) == 'V128({})'.format(V128)
+    assert repr(Value.v128(lanes)) == 'V128({})'.format(V128)
+
+def test_v128_from_signed_lanes():
+    assert repr(Value.v128((-1, 0, 0, 0), shape='i32x4')) == 'V128(4294967295)'
+
+def test_v128_from_float_lanes():
+    f32x4 = Value.v128((1.0, 2.0, 3.0, 4.0), shape='f32x4')
+    f64x2 = Value.v128((1.5, -2.5))
+
+    assert repr(f32x4) == 'V128({})'.format(int.from_bytes(struct.pack('<4f', 1.0, 2.0, 3.0, 4.0), 'little'))
+    assert repr(f64x2) == 'V128({})'.format(int.from_bytes(struct.pack('<2d', 1.5, -2.5), 'little'))
+
+def test_v128_lane_out_of_range():
+    with pytest.raises(ValueError) as context_manager:
+        Value.v128((256,) + (0,) * 15, shape='i8x16')
+
+    exception = context_manager.value
+    assert str(exception) == 'The lane `256` does not fit in 8 bits'
+
+def test_v128_wrong_number_of_lanes():
+    with pytest.raises(ValueError) as context_manager:
+        Value.v128((1, 2, 3), shape='i32x4')
+
+    exception = context_manager.value
+    assert str(exception) == 'A `v128` value has 4 lanes with this shape, given 3'
+
+def test_v128_unknown_shape():
+    with pytest.raises(ValueError):
+        Value.v128((1, 2, 3, 4), shape='i33x4')
+
+def simd_store():
+    return Store(engine.JIT(Compiler, features=engine.Features(simd=True)))
+
+def test_v128_round_trip_through_guest():
+    instance = Instance(
+        Module(
+            simd_store(),
+            """
+            (module
+              (func (export "identity") (param v128) (result v128)
+                local.get 0)
+              (func (export "add") (param v128 v128) (result v128)
+                local.get 0
+                local.get 1
+                i32x4.add))
+            """
+        )
+    )
+    exports = instance.exports
+
+    assert exports.identity(V128) == V128
+    assert exports.identity(V128_BYTES) == V128
+    assert exports.identity((0x03020100, 0x07060504, 0x0b0a0908, 0x0f0e0d0c)) == V128
+    assert exports.add((1, 2, 3, 4), (10, 20, 30, 40)) == int.from_bytes(
+        b''.join(lane.to_bytes(4, 'little') for lane in (11, 22, 33, 44)),
+        'little',
+    )
+
+def test_v128_host_function():
+    store = simd_store()
+    received = []
+
+    def host(value: wasmer.v128) -> wasmer.v128:
+        received.append(value)
+
+        return value.to_bytes(16, 'little')[::-1]
+
+    import_object = ImportObject()
+    import_object.register('env', {'host': Function(store, host)})
+
+    instance = Instance(
+        Module(
+            store,
+            """
+            (module
+              (import "env" "host" (func $host (param v128) (result v128)))
+              (func (export "call_host") (param v128) (result v128)
+                local.get 0
+                call $host))
+            """
+        ),
+        import_object,
+    )
+
+    assert instance.exports.call_host(V128_BYTES) == int.from_bytes(V128_BYTES[::-1], 'little')
+    assert received == [V128]
+
+def test_v128_global():
+    global_ = Global(simd_store(), Value.v128(V128_BYTES), mutable=True)
+
+    assert global_.value == V128
+
+    global_.value = (1, 2, 3, 4)
+
+    assert global_.value == 1 | 2 << 32 | 3 << 64 | 4 << 96
+
+def test_type():
+    assert Value.i32(1).type == Type.I32
+    assert Value.i64(1).type == Type.I64
+    assert Value.f32(1).type == Type.F32
+    assert Value.f64(1).type == Type.F64
+    assert Value.v128(1).type == Type.V128
+    assert Value.externref(None).type == Type.EXTERN_REF
+    assert Value.funcref(None).type == Type.FUNC_REF
+
+def test_value():
+    thing = object()
+
+    assert Value.i32(42).value == 42
+    assert Value.f64(4.2).value == 4.2
+    assert Value.externref(thing).value is thing
+
+def test_bits():
+    assert Value.i32(-1).bits == 0xffffffff
+    assert Value.i64(-1).bits == 0xffffffffffffffff
+    assert Value.f32(1.0).bits == 0x3f800000
+    assert Value.f64(1.0).bits == 0x3ff0000000000000
+    assert Value.v128(42).bits == 42
+
+def test_bits_of_reference():
+    with pytest.raises(TypeError):
+        Value.externref(None).bits
+
+def test_from_bits():
+    assert Value.f32_from_bits(0x3f800000) == Value.f32(1.0)
+    assert Value.f64_from_bits(0x3ff0000000000000) == Value.f64(1.0)
+    assert Value.f32_from_bits(0x7fc00001).bits == 0x7fc00001
+    assert Value.f64_from_bits(0x7ff8000000000001).bits == 0x7ff8000000000001
+
+def test_eq():
+    assert Value.i32(42) == Value.i32(42)
+    assert Value.i32(42) != Value.i32(43)
+    assert Value.i32(42) != Value.i64(42)
+    assert Value.i32(42) != 42
+    assert Value.f32_from_bits(0x7fc00001) == Value.f32_from_bits(0x7fc00001)
+    assert Value.f32_from_bits(0x7fc00001) != Value.f32_from_bits(0x7fc00002)
+    assert Value.f64(0.0) != Value.f64(-0.0)
+
+def test_eq_references():
+    thing = []
+
+    assert Value.externref(thing) == Value.externref(thing)
+    assert Value.externref(object) != Value.externref([])
+    assert Value.externref(None) == Value.externref(None)
+    assert Value.funcref(None) == Value.funcref(None)
+
+    function = Value.funcref(Function(Store(), lambda: None))
+
+    assert function == function
+    assert function != Value.funcref(None)
+
+def test_hash():
+    assert hash(Value.i32(42)) == hash(Value.i32(42))
+    assert len({Value.i32(42), Value.i32(42), Value.i64(42)}) == 2
+    assert Value.f64_from_bits(0x7ff8000000000001) in {Value.f64_from_bits(0x7ff8000000000001)}
+
+def test_hash_funcref():
+    with pytest.raises(TypeError):
+        hash(Value.funcref(Function(Store(), lambda: None)))